@@ -0,0 +1,28 @@
+// Fuzz target: arbitrary tweet text and search queries must never crash the contract
+// libFuzzer mutates the input bytes to reach new code paths - any panic is reported as a crash
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use near_sdk::{test_utils::VMContextBuilder, testing_env, AccountId};
+use near_twitter_example_rs::TwitterContract;
+
+fuzz_target!(|input: (String, String)| {
+    let (text, query) = input;
+    let mut builder = VMContextBuilder::new();
+    builder.predecessor_account_id("alice.near".parse::<AccountId>().unwrap());
+    testing_env!(builder.build());
+    let mut contract = TwitterContract::new();
+
+    let tweet = contract.post_tweet(text.clone(), None, None, None, None);
+    assert!(contract.verify_tweet_content(tweet.id, text));
+
+    // Queries with too many terms are rejected by design, so only search with the first words
+    let words: Vec<&str> = query
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .take(3)
+        .collect();
+    contract.search_tweets(words.join(" "), None, None, None);
+    contract.delete_tweet(tweet.id);
+    contract.gc_indexes(1);
+});
@@ -18,13 +18,594 @@
 // - Call Methods = Write operations (cost gas, like POST/PUT/DELETE requests)
 
 // Import NEAR SDK components - think of this as importing your web framework
-use near_sdk::store::IterableMap; // Like HashMap but optimized for blockchain storage
-use near_sdk::{env, near, AccountId, PanicOnDefault, Timestamp};
+use near_sdk::json_types::Base64VecU8; // Binary data encoded as base64 in JSON
+use near_sdk::store::{IterableMap, LookupMap, LookupSet, Vector}; // Like HashMap / HashSet / ArrayList but optimized for blockchain storage
+use near_sdk::{
+    env, near, AccountId, Gas, GasWeight, NearToken, PanicOnDefault, Promise, PromiseError,
+    PromiseResult, Timestamp,
+};
+use std::collections::{HashMap, HashSet}; // In-memory collections for per-call work (never stored on-chain)
+
+// ================================================================================================
+// CONFIGURATION CONSTANTS
+// ================================================================================================
+
+// How long users get between the announcement of a decommission and the account deletion
+// NEAR timestamps are in nanoseconds, so this is 30 days expressed in nanoseconds
+const DECOMMISSION_GRACE_PERIOD: Timestamp = 30 * 24 * 60 * 60 * 1_000_000_000;
+
+// Gas reserved for the callback that reports a failed account deletion
+const DECOMMISSION_CALLBACK_GAS: Gas = Gas::from_tgas(5);
+
+// Gas attached to each listener notification (see notify_listeners)
+// A fixed amount, so a write with 10 listeners always costs the same 50 Tgas extra at most
+const LISTENER_GAS: Gas = Gas::from_tgas(5);
+
+// One day in nanoseconds - activity stats are bucketed by "days since Unix epoch"
+const NANOS_PER_DAY: Timestamp = 24 * 60 * 60 * 1_000_000_000;
+
+// How long a co-authored draft waits for approvals before it expires (7 days)
+// Expired drafts can't be approved anymore, and anyone can cancel them to free the storage
+const DRAFT_TTL: Timestamp = 7 * NANOS_PER_DAY;
+
+// Maximum size of the admin council (see set_admin_council)
+const MAX_ADMIN_COUNCIL_MEMBERS: usize = 10;
+
+// How long an admin proposal can collect approvals before it expires (7 days)
+const ADMIN_PROPOSAL_TTL: Timestamp = 7 * NANOS_PER_DAY;
+
+// Gas attached to an approved proposal's owner method call, and to the callback that records
+// its outcome - fixed amounts, so the approving transaction knows what it has to pay for
+const ADMIN_PROPOSAL_CALL_GAS: Gas = Gas::from_tgas(50);
+const ADMIN_PROPOSAL_CALLBACK_GAS: Gas = Gas::from_tgas(5);
+
+// Owner methods a council proposal can't call: migrate runs right after a deployment, which
+// needs the account's key anyway, and the on_* methods are promise callbacks
+const NON_PROPOSABLE_METHODS: &[&str] = &[
+    "migrate",
+    "on_decommission_finalized",
+    "on_admin_proposal_executed",
+];
+
+// Maximum date range (in days) a single get_activity_stats call may cover
+const MAX_ACTIVITY_STATS_DAYS: u32 = 366;
+
+// Maximum range (in weeks) a single get_weekly_rollups call may cover
+const MAX_ROLLUP_WEEKS: u32 = 104;
+
+// Rising tweets: likes are counted in buckets of this many blocks (about 10 minutes), kept in a
+// ring of RISING_BUCKETS slots, so get_rising_tweets can look back at most about one day
+const RISING_BUCKET_BLOCKS: u64 = 600;
+const RISING_BUCKETS: u64 = 144;
+
+// Default look-back window of get_rising_tweets, in blocks (about one hour)
+const DEFAULT_RISING_WINDOW: u64 = 3_600;
+
+// Maximum number of distinct tweets counted per like bucket (later ones in a full bucket are not)
+const MAX_TWEETS_PER_LIKE_BUCKET: usize = 200;
+
+// Number of newest tweets get_ranked_feed scores (older tweets are never ranked)
+const MAX_RANKED_CANDIDATES: u64 = 200;
+
+// Maximum length of an off-chain content pointer (like a VARCHAR(256) column for URLs)
+const MAX_CONTENT_URI_LEN: usize = 256;
+
+// How many curated topics a single tweet can be tagged with
+const MAX_TOPICS_PER_TWEET: usize = 3;
+
+// Maximum number of federation peers (every new tweet costs one cross-contract call per peer)
+const MAX_FEDERATION_PEERS: usize = 10;
+
+// Maximum number of registered listener contracts (every event costs one call per listener)
+const MAX_LISTENERS: u32 = 10;
+
+// NEP-297 standard name and version of the events this contract emits
+const EVENT_STANDARD: &str = "near_twitter";
+const EVENT_STANDARD_VERSION: &str = "1.0.0";
+
+// JSON-LD context and public audience of ActivityStreams objects (used by Mastodon & co.)
+const ACTIVITYSTREAMS_CONTEXT: &str = "https://www.w3.org/ns/activitystreams";
+const ACTIVITYSTREAMS_PUBLIC: &str = "https://www.w3.org/ns/activitystreams#Public";
+
+// Number of most recent operations kept in the operation log (older ones are pruned)
+const OP_LOG_RETENTION: u64 = 10_000;
+
+// Maximum length of a client-supplied idempotency key (a UUID is 36 bytes)
+const MAX_IDEMPOTENCY_KEY_LEN: usize = 64;
+
+// Maximum number of tweet ids a v2 page walks before returning (deleted or filtered-out
+// tweets count too), so a single page can never run out of gas - the cursor continues from there
+const MAX_PAGE_SCAN: u64 = 1000;
+
+// Version of the stored state layout - bump it whenever a migration is required
+// 1 = the first deployed version (tweets and next_tweet_id only), 2 = this layout
+// migrate() upgrades version 1 state, see LegacyTwitterContract
+const STATE_SCHEMA_VERSION: u32 = 2;
+
+// Maximum number of tweet ids migrate_legacy_tweets converts per call (keeps gas bounded)
+const MAX_MIGRATION_BATCH: u32 = 100;
+
+// Maximum length of a short link destination (the practical URL limit of most browsers)
+const MAX_LINK_URL_LEN: usize = 2048;
+
+// Alphabet of short link codes - like the codes of URL shorteners such as bit.ly
+const SHORT_LINK_ALPHABET: &[u8] =
+    b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+// Limits for live spaces (audio/video rooms)
+const MAX_SPACE_TITLE_LEN: usize = 100;
+const MAX_SPACE_COHOSTS: usize = 10;
+
+// Limits for event tweets (meetups)
+const MAX_EVENT_TITLE_LEN: usize = 100;
+const MAX_EVENT_LOCATION_LEN: usize = 200;
+const MAX_EVENT_DETAILS_LEN: usize = 1000;
+
+// Maximum length of an attested external handle (GitHub allows 39 characters, X allows 15)
+const MAX_IDENTITY_HANDLE_LEN: usize = 64;
+
+// Persona (brand account) handles: lowercase letters, digits and "_", like a username
+const MAX_PERSONA_HANDLE_LEN: usize = 32;
+
+// Maximum number of active managers per persona
+const MAX_PERSONA_MANAGERS: usize = 10;
+
+// Maximum length of a settings language tag (BCP 47 tags like "en", "pt-BR" or "zh-Hant-TW")
+const MAX_LANGUAGE_TAG_LEN: usize = 35;
+
+// Version of the export_my_data record shapes - bump it when a section's records change
+const EXPORT_SCHEMA_VERSION: u32 = 1;
+
+// Sections export_my_data can page through
+const EXPORT_SECTIONS: &[&str] = &[
+    "tweets",
+    "settings",
+    "posting_delegates",
+    "linked_identities",
+    "personas",
+    "short_links",
+    "rsvps",
+];
+
+// Maximum number of archive records import_archive_chunk accepts per call (keeps gas bounded)
+const MAX_IMPORT_CHUNK: usize = 50;
+
+// Maximum number of tweets check_invariants inspects per call
+const MAX_INVARIANT_SAMPLE_SIZE: u32 = 100;
+
+// Number of most recent live tweets whose ids are hashed into a state snapshot
+const SNAPSHOT_RECENT_TWEETS: u64 = 100;
+
+// Tweet fields that can be requested from get_all_tweets_sparse
+const TWEET_FIELDS: &[&str] = &[
+    "id",
+    "author",
+    "text",
+    "timestamp",
+    "likes",
+    "license",
+    "content_hash",
+    "storage_tier",
+    "topics",
+    "coauthors",
+    "signer",
+    "imported",
+    "original_created_at",
+    "frozen",
+    "adult",
+    "persona",
+];
+
+// Limits of seed_demo_data (only compiled with the "demo-seed" feature), so one call fits in gas
+#[cfg(feature = "demo-seed")]
+const MAX_DEMO_ACCOUNTS: u32 = 50;
+#[cfg(feature = "demo-seed")]
+const MAX_DEMO_TWEETS: u32 = 100;
+
+// Texts seed_demo_data cycles through (a mix of hashtags, plain words and emoji, like real posts)
+#[cfg(feature = "demo-seed")]
+const DEMO_TEXTS: &[&str] = &[
+    "Just deployed my first smart contract on #NEAR 🚀",
+    "Coffee first, then code review ☕",
+    "Hot take: every backend engineer should try writing a contract",
+    "Reading about sharding and Nightshade today #near #blockchain",
+    "Shipped a new feature to testnet, feedback welcome!",
+    "What are your favorite Rust crates? #rust",
+    "gm 🌅",
+    "Storage staking finally makes sense to me",
+];
+
+// Machine-readable catalog of the public API returned by get_api_spec()
+// It is static data compiled into the contract, so reading it costs no storage
+// Keep it in sync when adding methods - test_get_api_spec checks the basics
+const API_METHODS: &[ApiMethodSpec] = &[
+    // Write methods
+    ApiMethodSpec::call(
+        "post_tweet",
+        &[
+            "text: String",
+            "license?: License",
+            "content_uri?: String",
+            "topics?: u32[]",
+            "idempotency_key?: String",
+        ],
+    ),
+    ApiMethodSpec::call(
+        "post_tweet_on_behalf",
+        &[
+            "author: AccountId",
+            "text: String",
+            "license?: License",
+            "content_uri?: String",
+            "topics?: u32[]",
+            "idempotency_key?: String",
+        ],
+    ),
+    ApiMethodSpec::call("like_tweet", &["tweet_id: u64", "idempotency_key?: String"]),
+    ApiMethodSpec::call(
+        "propose_coauthored_tweet",
+        &["text: String", "coauthors: AccountId[]"],
+    ),
+    ApiMethodSpec::call("approve_coauthorship", &["draft_id: u64"]),
+    ApiMethodSpec::call("reject_coauthorship", &["draft_id: u64"]),
+    ApiMethodSpec::call("cancel_draft", &["draft_id: u64"]),
+    ApiMethodSpec::call("grant_posting_delegate", &["delegate: AccountId"]),
+    ApiMethodSpec::call("revoke_posting_delegate", &["delegate: AccountId"]),
+    ApiMethodSpec::call("create_persona", &["handle: String"]),
+    ApiMethodSpec::call(
+        "add_persona_manager",
+        &["handle: String", "manager: AccountId"],
+    ),
+    ApiMethodSpec::call(
+        "remove_persona_manager",
+        &["handle: String", "manager: AccountId"],
+    ),
+    ApiMethodSpec::call(
+        "post_as_persona",
+        &[
+            "handle: String",
+            "text: String",
+            "license?: License",
+            "content_uri?: String",
+            "topics?: u32[]",
+            "idempotency_key?: String",
+        ],
+    ),
+    ApiMethodSpec::call("update_settings", &["patch: SettingsPatch"]),
+    ApiMethodSpec::call(
+        "import_archive_chunk",
+        &["records: ArchiveRecord[]", "account_id?: AccountId"],
+    ),
+    ApiMethodSpec::call("register_short_link", &["url: String"]),
+    ApiMethodSpec::call("record_click", &["code: String"]),
+    ApiMethodSpec::call(
+        "post_event",
+        &[
+            "title: String",
+            "starts_at: u64",
+            "location: String",
+            "details: String",
+        ],
+    ),
+    ApiMethodSpec::call("rsvp", &["tweet_id: u64", "status: RsvpStatus"]),
+    ApiMethodSpec::call(
+        "create_space",
+        &["title: String", "starts_at: u64", "stream_ref: String"],
+    ),
+    ApiMethodSpec::call("end_space", &["space_id: u64"]),
+    ApiMethodSpec::call("add_space_cohost", &["space_id: u64", "cohost: AccountId"]),
+    ApiMethodSpec::call(
+        "remove_space_cohost",
+        &["space_id: u64", "cohost: AccountId"],
+    ),
+    ApiMethodSpec::call(
+        "attest_identity",
+        &[
+            "account_id: AccountId",
+            "platform: IdentityPlatform",
+            "handle: String",
+            "proof_hash: Base64",
+        ],
+    ),
+    ApiMethodSpec::call("unlink_identity", &["platform: IdentityPlatform"]),
+    ApiMethodSpec::call("receive_federated_post", &["post: FederatedPost"]),
+    ApiMethodSpec::call("gc_indexes", &["limit: u32"]),
+    ApiMethodSpec::call("expire_tweets", &["limit: u32"]),
+    ApiMethodSpec::call("set_adult_flag", &["tweet_id: u64", "adult: bool"]),
+    ApiMethodSpec::call("delete_tweet", &["tweet_id: u64"]),
+    // Owner methods
+    ApiMethodSpec::owner("migrate", &[]),
+    ApiMethodSpec::owner("migrate_legacy_tweets", &["limit: u32"]),
+    ApiMethodSpec::owner("set_mode", &["mode: ContractMode"]),
+    ApiMethodSpec::owner("update_config", &["config: Config"]),
+    ApiMethodSpec::owner("freeze_tweet", &["tweet_id: u64"]),
+    ApiMethodSpec::owner("unfreeze_tweet", &["tweet_id: u64"]),
+    ApiMethodSpec::owner("moderate_adult_flag", &["tweet_id: u64", "adult: bool"]),
+    ApiMethodSpec::owner("create_topic", &["name: String", "description: String"]),
+    ApiMethodSpec::owner(
+        "register_listener",
+        &["contract_id: AccountId", "event_mask: u32"],
+    ),
+    ApiMethodSpec::owner("unregister_listener", &["contract_id: AccountId"]),
+    ApiMethodSpec::owner("create_snapshot", &[]),
+    ApiMethodSpec::owner("decommission", &[]),
+    ApiMethodSpec::owner("cancel_decommission", &[]),
+    ApiMethodSpec::owner("digest_final_state", &["limit: u32"]),
+    ApiMethodSpec::owner("remove_state_keys", &["keys: Base64VecU8[]"]),
+    ApiMethodSpec::owner("finalize_decommission", &["beneficiary_id: AccountId"]),
+    ApiMethodSpec::owner("on_decommission_finalized", &[]),
+    ApiMethodSpec::owner("on_admin_proposal_executed", &["proposal_id: u64"]),
+    ApiMethodSpec::owner(
+        "set_admin_council",
+        &["members: AccountId[]", "threshold: u32"],
+    ),
+    // Admin council methods (callable by council members)
+    ApiMethodSpec::call("propose_admin_action", &["method: String", "args: String"]),
+    ApiMethodSpec::call("approve", &["proposal_id: u64"]),
+    // Read methods
+    ApiMethodSpec::view(
+        "get_all_tweets",
+        &["from_index?: u64", "limit?: u64", "include_adult?: bool"],
+    )
+    .paginated("offset"),
+    ApiMethodSpec::view(
+        "get_all_tweets_sparse",
+        &[
+            "from_index?: u64",
+            "limit?: u64",
+            "fields?: String[]",
+            "include_adult?: bool",
+        ],
+    )
+    .paginated("offset"),
+    ApiMethodSpec::view(
+        "get_tweets_before",
+        &["timestamp: u64", "limit?: u64", "include_adult?: bool"],
+    )
+    .paginated("timestamp"),
+    ApiMethodSpec::view(
+        "get_tweets_after",
+        &["timestamp: u64", "limit?: u64", "include_adult?: bool"],
+    )
+    .paginated("timestamp"),
+    ApiMethodSpec::view("get_tweet_by_id", &["tweet_id: u64"]),
+    ApiMethodSpec::view("get_listeners", &[]),
+    ApiMethodSpec::view("get_federated_posts", &["from_index?: u32", "limit?: u32"])
+        .paginated("offset"),
+    ApiMethodSpec::view("get_op", &["op_id: u64"]),
+    ApiMethodSpec::view("get_admin_council", &[]),
+    ApiMethodSpec::view("get_admin_proposal", &["proposal_id: u64"]),
+    ApiMethodSpec::view("get_draft", &["draft_id: u64"]),
+    ApiMethodSpec::view("get_posting_delegates", &["account_id: AccountId"]),
+    ApiMethodSpec::view("get_persona", &["handle: String"]),
+    ApiMethodSpec::view("get_settings", &["account_id: AccountId"]),
+    ApiMethodSpec::view("get_event", &["tweet_id: u64"]),
+    ApiMethodSpec::view(
+        "get_event_attendees",
+        &[
+            "tweet_id: u64",
+            "status?: RsvpStatus",
+            "from_index?: u32",
+            "limit?: u32",
+        ],
+    )
+    .paginated("offset"),
+    ApiMethodSpec::view("get_live_spaces", &["from_index?: u32", "limit?: u32"])
+        .paginated("offset"),
+    ApiMethodSpec::view("get_space", &["space_id: u64"]),
+    ApiMethodSpec::view("get_linked_identities", &["account_id: AccountId"]),
+    ApiMethodSpec::view("resolve_link", &["code: String"]),
+    ApiMethodSpec::view("verify_tweet_content", &["tweet_id: u64", "text: String"]),
+    ApiMethodSpec::view(
+        "get_tweets_by_author",
+        &[
+            "author_id: AccountId",
+            "from_index?: u64",
+            "limit?: u64",
+            "include_adult?: bool",
+        ],
+    )
+    .paginated("offset"),
+    ApiMethodSpec::view("get_topics", &["from_index?: u32", "limit?: u32"]).paginated("offset"),
+    ApiMethodSpec::view(
+        "get_topic_feed",
+        &[
+            "topic_id: u32",
+            "from_index?: u64",
+            "limit?: u64",
+            "include_adult?: bool",
+        ],
+    )
+    .paginated("offset"),
+    ApiMethodSpec::view(
+        "search_tweets",
+        &[
+            "query: String",
+            "from_index?: u64",
+            "limit?: u64",
+            "include_adult?: bool",
+        ],
+    )
+    .paginated("offset"),
+    ApiMethodSpec::view("get_activity_stats", &["from_day: u32", "to_day: u32"]),
+    ApiMethodSpec::view(
+        "get_weekly_rollups",
+        &["account_id: AccountId", "from_week: u32", "to_week: u32"],
+    ),
+    ApiMethodSpec::view(
+        "get_persona_weekly_rollups",
+        &["handle: String", "from_week: u32", "to_week: u32"],
+    ),
+    ApiMethodSpec::view(
+        "get_rising_tweets",
+        &["window?: u64", "limit?: u32", "include_adult?: bool"],
+    ),
+    ApiMethodSpec::view("get_explore", &["limit?: u32", "include_adult?: bool"]),
+    ApiMethodSpec::view("get_ranked_feed", &["limit?: u32", "include_adult?: bool"]),
+    ApiMethodSpec::view("get_config", &[]),
+    ApiMethodSpec::view("get_rate_limit_config", &[]),
+    ApiMethodSpec::view("get_rate_limit_metrics", &[]),
+    ApiMethodSpec::view(
+        "get_tweets_v2",
+        &["cursor?: u64", "limit?: u64", "include_adult?: bool"],
+    )
+    .paginated("cursor"),
+    ApiMethodSpec::view(
+        "get_tweets_by_author_v2",
+        &[
+            "author_id: AccountId",
+            "cursor?: u64",
+            "limit?: u64",
+            "include_adult?: bool",
+        ],
+    )
+    .paginated("cursor"),
+    ApiMethodSpec::view(
+        "export_my_data",
+        &[
+            "account_id: AccountId",
+            "section: String",
+            "cursor?: u64",
+            "limit?: u64",
+        ],
+    )
+    .paginated("cursor"),
+    ApiMethodSpec::view("get_activitypub_note", &["tweet_id: u64"]),
+    ApiMethodSpec::view("get_activitypub_person", &["account_id: AccountId"]),
+    ApiMethodSpec::view("get_activitypub_create", &["tweet_id: u64"]),
+    ApiMethodSpec::view(
+        "check_invariants",
+        &["sample_size: u32", "from_index?: u32"],
+    ),
+    ApiMethodSpec::view("get_snapshots", &["from_index?: u32", "limit?: u32"]).paginated("offset"),
+    ApiMethodSpec::view("get_inclusion_proof", &["tweet_id: u64", "tree_size?: u64"]),
+    ApiMethodSpec::view("get_status", &[]),
+    ApiMethodSpec::view("get_method_metrics", &[]),
+    ApiMethodSpec::view("get_api_spec", &[]),
+];
+
+// Fixed error messages a client can match on (the contract fails calls with these panic messages)
+const API_ERRORS: &[&str] = &[
+    "Only the contract owner can call this method",
+    "Owner methods need admin council approval: use propose_admin_action",
+    "The contract account can't be an admin council member",
+    "Council threshold must be between 1 and the number of members",
+    "Only admin council members can propose or approve admin actions",
+    "Admin action args must be a JSON object",
+    "Admin proposal not found",
+    "Admin proposal was already executed",
+    "Admin proposal was made for a previous council",
+    "Admin proposal has expired",
+    "Admin proposal already approved by this member",
+    "Contract is in read-only mode: new content is disabled",
+    "Contract is being decommissioned: writes are frozen",
+    "Tweet exceeds max_onchain_bytes: content_uri is required",
+    "content_uri must start with ipfs:// or ar://",
+    "content_uri is too long",
+    "Unknown topic id",
+    "Idempotency key was already used for a different operation",
+    "The original tweet has been deleted",
+    "Tweet not found",
+    "No contract state to migrate",
+    "No legacy tweets to migrate",
+    "Legacy tweets are still being migrated",
+    "Tweet is frozen by a legal hold",
+    "Only the author can label a tweet: moderators use moderate_adult_flag",
+    "Only moderators can remove an adult label",
+    "Decommission grace period is over: adult labels can't change",
+    "Decommission grace period is over: legal holds can't change",
+    "Caller is not a posting delegate of this author",
+    "Rate limit exceeded: try again later",
+    "Batch is larger than the rate limit capacity",
+    "Rate limit capacity and refill_interval must be positive",
+    "Ranking recency_half_life must be positive",
+    "Cannot delegate posting rights to yourself",
+    "Account is already a posting delegate",
+    "Account is not a posting delegate",
+    "Invalid persona handle",
+    "Persona handle is already taken",
+    "Persona not found",
+    "Only the persona owner can manage it",
+    "Account is already a persona manager",
+    "Persona has too many managers",
+    "Account is not a persona manager",
+    "Caller can't post as this persona",
+    "Pass either default_license or clear_default_license, not both",
+    "Invalid language tag",
+    "Short link url must start with https:// or http://",
+    "Short link url is too long",
+    "Short link not found",
+    "Event location or details are too long",
+    "Event not found",
+    "Event announcement exceeds max_onchain_bytes: shorten the title, location or details",
+    "Space announcement exceeds max_onchain_bytes: shorten the title or stream_ref",
+    "Space not found or already ended",
+    "Only the host can manage this space",
+    "Account already hosts this space",
+    "Only configured attesters can attest identities",
+    "Invalid identity handle",
+    "proof_hash must be a SHA-256 hash (32 bytes)",
+    "A co-authored tweet needs at least one co-author besides the proposer",
+    "Co-authored tweets must fit within max_onchain_bytes",
+    "Draft not found",
+    "Only a listed co-author can approve this draft",
+    "Co-authorship already approved",
+    "Only a listed co-author can reject this draft",
+    "Draft has expired",
+    "Only the proposer can cancel a draft before it expires",
+    "Only federation peers can forward posts",
+    "Topic description is too long",
+    "A topic with this name already exists",
+    "Invalid event mask",
+    "Listener is not registered",
+    "Decommission has already been started",
+    "Decommission has not been started",
+    "Decommission grace period has not ended yet",
+    "Decommission grace period is over: tweets can't be deleted",
+    "State removal has started: the decommission can't be cancelled",
+    "The final state digest is not complete yet",
+    "The contract state record is removed together with the account",
+    "Account deletion is already in progress",
+];
+
+// Maximum number of co-authors (besides the proposer) on a co-authored tweet
+const MAX_COAUTHORS: usize = 5;
+
+// Maximum lengths for topic names and descriptions (in bytes)
+const MAX_TOPIC_NAME_LEN: usize = 50;
+const MAX_TOPIC_DESCRIPTION_LEN: usize = 280;
+
+// Keyword search index limits - they keep the index (and the gas of posting) bounded
+const MIN_TERM_LEN: usize = 2; // Shorter words are too common to be useful
+const MAX_TERM_LEN: usize = 32; // Longer "words" are usually URLs or noise
+const MAX_TERMS_PER_TWEET: usize = 20; // Only the first distinct words of a tweet are indexed
+const MAX_QUERY_TERMS: usize = 3; // search_tweets supports an AND of a few terms
+
+// Common words that are not worth indexing (like a full-text search engine's stopword list)
+const STOPWORDS: &[&str] = &[
+    "an", "and", "are", "as", "at", "be", "but", "by", "for", "from", "has", "have", "in", "is",
+    "it", "its", "of", "on", "or", "so", "that", "the", "this", "to", "was", "we", "were", "will",
+    "with", "you",
+];
 
 // ================================================================================================
 // DATA STRUCTURES
 // ================================================================================================
 
+// TweetId identifies a tweet - a newtype around u64 (like a typed primary key)
+// so that tweet ids can't be mixed up with draft, op or topic ids by clients or internal code.
+// It is encoded exactly like a plain number in JSON and Borsh, so the wire format is unchanged
+#[near(serializers = [borsh, json])]
+#[serde(transparent)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TweetId(pub u64);
+
+impl std::fmt::Display for TweetId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
 // Tweet represents a single tweet in our social media platform
 // The #[near] attribute automatically handles serialization/deserialization
 // Think of this as your API response/request DTOs, but for blockchain
@@ -32,7 +613,7 @@ use near_sdk::{env, near, AccountId, PanicOnDefault, Timestamp};
 #[derive(Clone, Debug, PartialEq)]
 pub struct Tweet {
     // Unique identifier for this tweet (like auto-increment ID in SQL)
-    pub id: u64,
+    pub id: TweetId,
 
     // NEAR account that created this tweet (like user_id in traditional apps)
     // AccountId is NEAR's version of a username/user identifier
@@ -47,81 +628,4725 @@ pub struct Tweet {
 
     // Number of likes this tweet has received (like a counter field)
     pub likes: u64,
+
+    // Optional license chosen by the author at post time (None = not specified)
+    // Tells downstream re-use tools (archives, aggregators, AI datasets) what they may do
+    pub license: Option<License>,
+
+    // SHA-256 hash of the canonicalized text (see canonicalize_text below)
+    // Lets mirrors and archives prove their copy matches the on-chain record,
+    // similar to publishing a checksum next to a downloadable file
+    pub content_hash: Base64VecU8,
+
+    // Where the text lives: on-chain in `text`, or off-chain behind a pointer
+    // Clients check this to know whether they need to fetch the content from IPFS/Arweave
+    pub storage_tier: StorageTier,
+
+    // Curated topics this tweet is tagged with (ids from the topics registry)
+    // Like a many-to-many tweet_topics join table, stored inline since there are at most a few
+    pub topics: Vec<u32>,
+
+    // Additional authors who approved publishing this tweet (empty for regular tweets)
+    // `author` is the account that proposed it; clients show all of them in the byline
+    pub coauthors: Vec<AccountId>,
+
+    // Account that actually signed the transaction when a posting delegate (ghost-writer)
+    // posted on the author's behalf; None when the author posted it themselves
+    pub signer: Option<AccountId>,
+
+    // True for tweets recreated from an archive of another platform (see import_archive_chunk)
+    pub imported: bool,
+
+    // Original creation time of an imported tweet; `timestamp` is when it was imported
+    pub original_created_at: Option<Timestamp>,
+
+    // Under a legal hold set by the owner (see freeze_tweet): the tweet can't be deleted
+    pub frozen: bool,
+
+    // Age-restricted content (see set_adult_flag): left out of list views unless requested
+    pub adult: bool,
+
+    // Persona (brand account) the tweet was posted as; `author` is the persona's owner and
+    // `signer` the manager who posted it, if that wasn't the owner (see post_as_persona)
+    pub persona: Option<String>,
 }
 
-// ================================================================================================
-// SMART CONTRACT STATE
-// ================================================================================================
+// Draft is a co-authored tweet waiting for approval from all of its co-authors
+// Like a pending row in a "tweet_drafts" table that is moved to "tweets" once approved
+#[near(serializers = [borsh, json])]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Draft {
+    // Unique identifier for this draft (separate sequence from tweet ids)
+    pub id: u64,
 
-// TwitterContract is the main contract state - think of this as your application's
-// in-memory state that persists between requests (like instance variables in a service class)
-//
-// In traditional backend:
-// - You'd have a database to store tweets
-// - You'd have application state in memory/cache
-//
-// In NEAR:
-// - Contract state IS your database (stored on blockchain)
-// - State persists between function calls
-// - State is automatically loaded/saved by NEAR runtime
-#[near(contract_state)]
-#[derive(PanicOnDefault)] // Prevents accidental initialization without proper setup
-pub struct TwitterContract {
-    // Storage for all tweets - like your main tweets table
-    // IterableMap is NEAR's version of HashMap optimized for blockchain storage
-    // Key: tweet_id, Value: Tweet object
-    tweets: IterableMap<u64, Tweet>,
+    // Account that proposed the tweet - becomes the tweet's `author`
+    pub author: AccountId,
 
-    // Counter for generating unique tweet IDs (like auto-increment in SQL)
-    // This ensures each tweet gets a unique identifier
-    next_tweet_id: u64,
+    // The proposed tweet content
+    pub text: String,
+
+    // Accounts that must approve before the tweet is published
+    pub coauthors: Vec<AccountId>,
+
+    // Co-authors who have approved so far
+    pub approvals: Vec<AccountId>,
+
+    // When the draft was proposed
+    pub created_at: Timestamp,
+
+    // When the draft stops accepting approvals (created_at + DRAFT_TTL)
+    pub expires_at: Timestamp,
 }
 
-// ================================================================================================
-// CONTRACT IMPLEMENTATION
-// ================================================================================================
+// AdminCouncil is an M-of-N group of accounts that replaces the contract account's key for
+// owner methods - like requiring two approvals on a production change instead of one admin
+#[near(serializers = [borsh, json])]
+#[derive(Clone, Debug, PartialEq)]
+pub struct AdminCouncil {
+    pub members: Vec<AccountId>,
 
-// The #[near] attribute marks this implementation block as containing contract methods
-// Think of these as your REST API endpoints, but they run on the blockchain
-#[near]
-impl TwitterContract {
-    // ============================================================================================
-    // INITIALIZATION METHOD
-    // ============================================================================================
+    // Approvals an admin proposal needs before it runs (the M of M-of-N)
+    pub threshold: u32,
 
-    // Contract constructor - called once when the contract is first deployed
-    // Similar to database migrations or initial setup in traditional backends
-    // The #[init] attribute marks this as the initialization method
-    #[init]
-    pub fn new() -> Self {
-        Self {
-            // Initialize tweet storage with a unique storage prefix
-            // "b't'" is a byte string prefix to avoid storage conflicts
-            // Think of this as creating a table in your database
-            tweets: IterableMap::new(b"t"),
+    // Id of the first proposal made for this council; older proposals can't be approved
+    pub first_proposal_id: u64,
+}
+
+// AdminProposalStatus is where an admin proposal is in its lifecycle
+#[near(serializers = [borsh, json])]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AdminProposalStatus {
+    // Collecting approvals
+    Pending,
+
+    // Reached the threshold and the call was dispatched - waiting for its outcome
+    Executing,
+
+    // The call succeeded
+    Executed,
+
+    // The call failed (panicked or ran out of gas) - propose it again to retry
+    Failed,
+}
+
+// AdminProposal is an owner method call waiting for council approvals
+// Like a change request in a deployment tool: kept after execution as an audit trail
+#[near(serializers = [borsh, json])]
+#[derive(Clone, Debug, PartialEq)]
+pub struct AdminProposal {
+    pub id: u64,
+    pub proposer: AccountId,
+
+    // Owner method to call on this contract, and its arguments as a JSON object
+    pub method: String,
+    pub args: String,
+
+    // Council members who approved so far (the proposer included)
+    pub approvals: Vec<AccountId>,
+
+    pub created_at: Timestamp,
+
+    // When the proposal stops accepting approvals (created_at + ADMIN_PROPOSAL_TTL)
+    pub expires_at: Timestamp,
+
+    pub status: AdminProposalStatus,
+}
+
+// Delegation records that an account allowed another account to post on its behalf
+// Revoked delegations are kept (with revoked_at set) as an audit trail, like soft deletes
+#[near(serializers = [borsh, json])]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Delegation {
+    // Account allowed to post with the granting account as the byline
+    pub delegate: AccountId,
+
+    // When the delegation was granted
+    pub granted_at: Timestamp,
+
+    // When the delegation was revoked (None while it is active)
+    pub revoked_at: Option<Timestamp>,
+}
+
+// Persona is a brand identity inside the contract (not a separate NEAR account)
+// Like an organization account on GitHub: one owner, several members allowed to act for it
+#[near(serializers = [borsh, json])]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Persona {
+    pub handle: String,
+
+    // Account that created the persona; it manages the managers and is the byline author
+    pub owner: AccountId,
+    pub created_at: Timestamp,
+
+    // Manager grants, active and revoked (kept for attribution, like an audit trail)
+    pub managers: Vec<Delegation>,
+}
+
+// AccountSettings holds an account's defaults and preferences
+// Like a user_settings table row: one per account, created on first update
+#[near(serializers = [borsh, json])]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AccountSettings {
+    // License post_tweet uses when none is given (None = no license, like before)
+    pub default_license: Option<License>,
+
+    // Preferred language as a BCP 47 tag (e.g. "en", "pt-BR") - used by clients
+    pub language: Option<String>,
+
+    // Whether the account wants sensitive content shown - used by clients
+    // Self-attested: clients pass it as `include_adult` to the list views
+    pub show_sensitive_content: bool,
+
+    // Auto-delete the account's tweets this many days after they were written (None = keep)
+    // Enforced by the expire_tweets maintenance sweep
+    pub expire_after_days: Option<u32>,
+}
+
+// SettingsPatch is the argument of update_settings - like the body of a PATCH request:
+// fields that are left out (null) keep their current value
+#[near(serializers = [json])]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SettingsPatch {
+    pub default_license: Option<License>,
+
+    // true removes the default license (a null default_license means "keep", like the others)
+    #[serde(default)]
+    pub clear_default_license: bool,
+
+    // An empty string clears the language
+    pub language: Option<String>,
+
+    pub show_sensitive_content: Option<bool>,
+
+    // 0 turns auto-expiry off
+    pub expire_after_days: Option<u32>,
+}
+
+// ShortLink maps a compact code that tweets can embed to its full destination
+// Like a row in a URL shortener's links table, but the destination is public and verifiable
+#[near(serializers = [borsh, json])]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ShortLink {
+    // Code to embed in tweets (base62, e.g. "1a")
+    pub code: String,
+
+    // Full destination URL
+    pub url: String,
+
+    // Account that registered the link
+    pub owner: AccountId,
+
+    // When the link was registered
+    pub created_at: Timestamp,
+
+    // Clicks reported through record_click
+    pub clicks: u64,
+}
+
+// Space is a live audio/video room registered on-chain
+// The stream itself runs off-chain (e.g. a LiveKit/Jitsi room or an HLS URL); the contract is
+// just the directory - like a rooms table in a video conferencing backend
+#[near(serializers = [borsh, json])]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Space {
+    pub id: u64,
+
+    // Account that created the space and manages it
+    pub host: AccountId,
+
+    // Accounts the host invited to co-host
+    pub cohosts: Vec<AccountId>,
+
+    pub title: String,
+
+    // When the space starts (it may be scheduled for later)
+    pub starts_at: Timestamp,
+
+    // Where clients join the stream (URL or room id of the streaming service)
+    pub stream_ref: String,
+
+    // Tweet announcing the space in the host's timeline
+    pub announcement_id: TweetId,
+
+    // When the host ended the space (None while it is live or upcoming)
+    pub ended_at: Option<Timestamp>,
+}
+
+// EventDetails is the structured part of an event tweet (a meetup announcement)
+// The tweet keeps a readable summary; this holds the fields clients render natively
+// Like an events table with a one-to-one relation to posts
+#[near(serializers = [borsh, json])]
+#[derive(Clone, Debug, PartialEq)]
+pub struct EventDetails {
+    // The tweet announcing the event (also the event's id)
+    pub tweet_id: TweetId,
+
+    pub title: String,
+
+    pub starts_at: Timestamp,
+
+    // Venue or "online"
+    pub location: String,
+
+    pub details: String,
+
+    // How many accounts answered (list them with get_event_attendees)
+    pub rsvp_count: u32,
+}
+
+// RsvpStatus is an answer to an event invitation
+#[near(serializers = [borsh, json])]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RsvpStatus {
+    Going,
+    Interested,
+    NotGoing,
+}
+
+// Rsvp is one account's answer to an event
+#[near(serializers = [borsh, json])]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rsvp {
+    pub account_id: AccountId,
+    pub status: RsvpStatus,
+
+    // When the account last changed its answer
+    pub updated_at: Timestamp,
+}
+
+// IdentityPlatform is an external service an account can be linked to
+#[near(serializers = [borsh, json])]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum IdentityPlatform {
+    X, // X (formerly Twitter)
+    GitHub,
+}
+
+impl IdentityPlatform {
+    // Display name, e.g. for profile fields
+    pub fn name(self) -> &'static str {
+        match self {
+            IdentityPlatform::X => "X",
+            IdentityPlatform::GitHub => "GitHub",
+        }
+    }
+}
+
+// LinkedIdentity records that an attester verified an account's external identity
+// Like a "verified accounts" row written by a trusted verification service (think Keybase)
+#[near(serializers = [borsh, json])]
+#[derive(Clone, Debug, PartialEq)]
+pub struct LinkedIdentity {
+    pub platform: IdentityPlatform,
+
+    // Handle on the platform, without a leading "@"
+    pub handle: String,
+
+    // SHA-256 of the off-chain proof the attester checked (e.g. a signed gist or post),
+    // so anyone holding the proof can verify it is the one that was attested
+    pub proof_hash: Base64VecU8,
+
+    // Attester account that verified the link
+    pub attester: AccountId,
+
+    // When the link was attested
+    pub attested_at: Timestamp,
+}
+
+// StorageTier tells clients where a tweet's text is stored
+// Think of it like a row that either holds the data inline or a link to a blob in S3
+#[near(serializers = [borsh, json])]
+#[derive(Clone, Debug, PartialEq)]
+pub enum StorageTier {
+    // The full text is stored in the contract (`text` field)
+    OnChain,
+
+    // Only the content hash is stored on-chain; the text is at `uri` (ipfs:// or ar://)
+    // and `text` is empty. Clients verify the fetched copy with verify_tweet_content
+    OffChain { uri: String },
+}
+
+// License describes how others may re-use a tweet's content
+// Think of this as the "license" field you'd find on a photo or dataset upload form
+#[near(serializers = [borsh, json])]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum License {
+    // No re-use without the author's permission (the default copyright situation)
+    AllRightsReserved,
+
+    // Creative Commons licenses - see https://creativecommons.org/licenses/
+    Cc0,      // Public domain dedication, no conditions
+    CcBy,     // Attribution required
+    CcBySa,   // Attribution + share-alike
+    CcByNc,   // Attribution + non-commercial
+    CcByNcSa, // Attribution + non-commercial + share-alike
+    CcByNd,   // Attribution + no derivatives
+    CcByNcNd, // Attribution + non-commercial + no derivatives
+}
+
+// TwitterEvent is what listener contracts get notified about
+// Each event has a bit in the listener's event mask, like a subscription filter on a webhook
+#[near(serializers = [borsh, json])]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TwitterEvent {
+    TweetPosted,  // mask bit 1
+    TweetLiked,   // mask bit 2
+    TweetDeleted, // mask bit 4
+}
+
+impl TwitterEvent {
+    // Bit of this event in an event mask
+    pub fn mask(self) -> u32 {
+        match self {
+            TwitterEvent::TweetPosted => 1,
+            TwitterEvent::TweetLiked => 2,
+            TwitterEvent::TweetDeleted => 4,
+        }
+    }
+}
+
+impl TwitterEvent {
+    // Event name in NEP-297 event logs
+    pub fn name(self) -> &'static str {
+        match self {
+            TwitterEvent::TweetPosted => "tweet_posted",
+            TwitterEvent::TweetLiked => "tweet_liked",
+            TwitterEvent::TweetDeleted => "tweet_deleted",
+        }
+    }
+}
+
+// Every event bit a listener can subscribe to
+const ALL_EVENTS_MASK: u32 = 1 | 2 | 4;
+
+// Operation is one entry of the operation log: a user write that landed on-chain
+// Clients doing optimistic UI updates use it to reconcile which of their pending writes
+// went through - like looking up a request id in an audit log
+#[near(serializers = [borsh, json])]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Operation {
+    // Monotonically increasing id, assigned in execution order
+    pub op_id: u64,
+
+    pub kind: OperationKind,
+
+    // Account that performed the write
+    pub account_id: AccountId,
+
+    // Record the write applied to (None for delegation changes)
+    pub target: Option<OpTarget>,
+
+    // When the write landed
+    pub timestamp: Timestamp,
+
+    // Client-supplied idempotency key of the write, if any
+    pub idempotency_key: Option<String>,
+}
+
+// OperationKind is the type of a logged user write
+#[near(serializers = [borsh, json])]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OperationKind {
+    PostTweet,
+    LikeTweet,
+    DeleteTweet,
+    ProposeDraft,
+    ApproveDraft,
+    CancelDraft,
+    RejectDraft,
+    DiscardDraft,
+    GrantPostingDelegate,
+    RevokePostingDelegate,
+    FreezeTweet,
+    UnfreezeTweet,
+    UpdateSettings,
+    CreatePersona,
+    AddPersonaManager,
+    RemovePersonaManager,
+    RegisterShortLink,
+    RecordClick,
+    Rsvp,
+    CreateSpace,
+    EndSpace,
+    AddSpaceCohost,
+    RemoveSpaceCohost,
+    AttestIdentity,
+    UnlinkIdentity,
+    SetAdultFlag,
+    ExpireTweet,
+    ProposeAdminAction,
+    ApproveAdminAction,
+}
+
+// OpTarget is the record an operation applied to - typed, so a tweet id can't be mistaken
+// for a draft id (in JSON: {"Tweet": 7} or {"Draft": 3})
+#[near(serializers = [borsh, json])]
+#[derive(Clone, Debug, PartialEq)]
+pub enum OpTarget {
+    Tweet(TweetId),
+    Draft(u64),
+    Persona(String),
+    ShortLink(String),
+    Space(u64),
+    Account(AccountId),
+    AdminProposal(u64),
+}
+
+// WithOpId wraps the result of a write with the op id it was logged under (see get_op)
+// In JSON the result's fields are inlined next to op_id, so existing clients keep working:
+// {"op_id": 12, "id": 7, "author": "alice.near", ...}
+#[near(serializers = [json])]
+#[derive(Clone, Debug, PartialEq)]
+pub struct WithOpId<T> {
+    pub op_id: u64,
+
+    #[serde(flatten)]
+    pub value: T,
+}
+
+impl<T> std::ops::Deref for WithOpId<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+// ContractMode controls which operations the contract currently accepts
+// Think of this as the "maintenance mode" switch many web frameworks provide
+#[near(serializers = [borsh, json])]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ContractMode {
+    // Normal operation - every method is available
+    Active,
+
+    // Read-only maintenance mode (useful during migrations):
+    // - All views keep working
+    // - Deleting existing content keeps working
+    // - Creating new content (posting tweets) is rejected
+    ReadOnly,
+}
+
+// Topic is an entry in the curated topics registry managed by the owner
+// Unlike free-form hashtags, topics form a fixed taxonomy - like categories in a forum
+#[near(serializers = [borsh, json])]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Topic {
+    // Topic identifier (its position in the registry, like an auto-increment ID)
+    pub id: u32,
+
+    // Short unique display name, e.g. "Rust" or "DeFi"
+    pub name: String,
+
+    // What belongs in this topic
+    pub description: String,
+}
+
+// WeeklyRollup is the engagement of one author during one week - like a row in a
+// creator analytics table (author_id, week, posts, likes_received)
+#[near(serializers = [borsh, json])]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WeeklyRollup {
+    // Week number since Unix epoch (block_timestamp / 7 days; week 0 starts 1970-01-01)
+    pub week: u32,
+
+    // Tweets the author posted during the week
+    pub posts: u64,
+
+    // Likes the author's tweets received during the week (whenever they were posted)
+    pub likes_received: u64,
+}
+
+// DailyStats are the activity counters of one day - like a row in a daily_stats rollup table
+// Dashboards can chart growth from these without replaying the whole history
+#[near(serializers = [borsh, json])]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DailyStats {
+    // Day number since Unix epoch (block_timestamp / 1 day)
+    pub day: u32,
+
+    // Tweets posted during the day
+    pub tweets_posted: u64,
+
+    // Likes given during the day
+    pub likes: u64,
+
+    // Accounts that posted or liked for the first time during the day
+    pub new_accounts: u64,
+}
+
+// FederatedPost is the summary of a tweet exchanged between peer contracts
+// Like a webhook payload sent to partner services when something is published
+#[near(serializers = [borsh, json])]
+#[derive(Clone, Debug, PartialEq)]
+pub struct FederatedPost {
+    // Peer contract the post came from - set by the receiver from the caller's account,
+    // so a peer cannot claim to forward posts on behalf of another community
+    pub origin: AccountId,
+
+    // Tweet id on the origin contract
+    pub tweet_id: TweetId,
+
+    // Author account on the origin contract
+    pub author: AccountId,
+
+    // Tweet text (empty if the origin stores it off-chain)
+    pub text: String,
+
+    // SHA-256 of the canonicalized text, as on the origin contract
+    pub content_hash: Base64VecU8,
+
+    // When the tweet was posted on the origin contract
+    pub timestamp: Timestamp,
+}
+
+// Config holds the owner-tunable settings of the contract
+// Similar to an application config file, but stored on-chain and changeable at runtime
+#[near(serializers = [borsh, json])]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Config {
+    // Tweets longer than this many bytes must be stored off-chain (hash + pointer only)
+    // None disables the hybrid mode: every tweet is stored fully on-chain
+    pub max_onchain_bytes: Option<u32>,
+
+    // Maximum number of tweet ids kept per search term (older ones are dropped first)
+    // 0 disables indexing of new tweets for keyword search
+    pub max_postings_per_term: u32,
+
+    // Peer twitter-example contracts (other communities) to federate with
+    // New tweets are forwarded to every peer, and only peers may push posts to us
+    pub federation_peers: Vec<AccountId>,
+
+    // Oracle/attester accounts trusted to verify external identities (see attest_identity)
+    // Like the list of identity providers an SSO setup trusts
+    pub attesters: Vec<AccountId>,
+
+    // Per-account limit on content writes such as posts and likes (None disables rate limiting)
+    pub rate_limit: Option<RateLimitConfig>,
+
+    // Weights of get_ranked_feed, public so anyone can audit how the feed is ordered
+    pub ranking: RankingConfig,
+}
+
+// RankingConfig holds the weights of the ranked feed
+// Every tweet starts at 1000 points plus `like_weight` per like, and its score then decays
+// hyperbolically: score = points * half_life / (half_life + age), so it halves after
+// `recency_half_life` nanoseconds - like the gravity setting of a Hacker News-style ranker
+#[near(serializers = [borsh, json])]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RankingConfig {
+    pub like_weight: u32,
+    pub recency_half_life: Timestamp,
+}
+
+// RateLimitConfig describes a token bucket: every account may burst up to `capacity` writes,
+// then earns one write back every `refill_interval` nanoseconds
+// Like a per-user token bucket in an API gateway (nginx limit_req, Redis-based limiters)
+#[near(serializers = [borsh, json])]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RateLimitConfig {
+    pub capacity: u32,
+    pub refill_interval: Timestamp,
+}
+
+// Token bucket state of one account - a fixed 12 bytes however active the account is,
+// unlike a list of recent write timestamps that grows with every write
+#[near(serializers = [borsh])]
+#[derive(Clone, Copy, Debug)]
+struct RateBucket {
+    // Writes the account may still make right now
+    tokens: u32,
+
+    // Time the last refill was accounted for
+    updated_at: Timestamp,
+}
+
+// Likes given during one RISING_BUCKET_BLOCKS-long bucket of blocks, per tweet
+// Stored in a ring slot that is overwritten when the slot is reused for a newer bucket,
+// like a fixed-size circular buffer of per-minute counters in a metrics agent
+#[near(serializers = [borsh])]
+#[derive(Clone, Debug)]
+struct LikeBucket {
+    // Bucket number (block_height / RISING_BUCKET_BLOCKS)
+    bucket: u64,
+
+    // (tweet id, likes during the bucket), in order of the first like
+    likes: Vec<(TweetId, u32)>,
+}
+
+// RisingTweet is one entry of get_rising_tweets() - a tweet with its recent engagement
+#[near(serializers = [json])]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RisingTweet {
+    pub tweet: Tweet,
+
+    // Likes received within the requested window
+    pub recent_likes: u64,
+
+    // Ranking score: recent_likes * 1000 / (age in hours + 2), so new tweets
+    // with a burst of likes outrank old tweets collecting likes at the same pace
+    pub score: u64,
+}
+
+// RankedTweet is one entry of get_ranked_feed() - a tweet with the score it was ranked by
+#[near(serializers = [json])]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RankedTweet {
+    pub tweet: Tweet,
+    pub score: u64,
+}
+
+// TrendingTopic is a curated topic ranked by the recent likes of its rising tweets
+#[near(serializers = [json])]
+#[derive(Clone, Debug, PartialEq)]
+pub struct TrendingTopic {
+    pub topic: Topic,
+    pub recent_likes: u64,
+}
+
+// ExplorePage is returned by get_explore() - the sections of an explore screen in one response,
+// like the aggregated payload of a backend-for-frontend endpoint
+#[near(serializers = [json])]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExplorePage {
+    // Same as get_rising_tweets with the default window
+    pub rising: Vec<RisingTweet>,
+
+    // Topics whose tweets are rising, most recent likes first (ties: lower topic id first)
+    pub trending_topics: Vec<TrendingTopic>,
+}
+
+// RateLimitMetrics is returned by get_rate_limit_metrics() - like the counters a rate-limiting
+// proxy exports to Prometheus
+// Rejected writes can't be counted on-chain (the failed call's state changes are rolled back),
+// look for failed transactions with the "Rate limit exceeded" error instead
+#[near(serializers = [json])]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RateLimitMetrics {
+    // Accounts with a token bucket in storage (each costs a fixed few bytes)
+    pub tracked_accounts: u64,
+
+    // Tokens consumed by writes that went through the rate limiter (a batch counts per item)
+    pub metered_writes: u64,
+}
+
+// Default settings used when the contract is initialized
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            max_onchain_bytes: None,
+            max_postings_per_term: 1000,
+            federation_peers: Vec::new(),
+            attesters: Vec::new(),
+            rate_limit: None,
+            ranking: RankingConfig {
+                like_weight: 100,
+                recency_half_life: 6 * 60 * 60 * 1_000_000_000,
+            },
+        }
+    }
+}
+
+// ActivityNote is a tweet shaped as an ActivityStreams "Note" object
+// A bridge service can forward it to Mastodon and other ActivityPub servers almost as-is
+// Ids use a near://<contract>/... scheme; the bridge maps them to its own https URLs
+#[near(serializers = [json])]
+#[derive(Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityNote {
+    // JSON-LD context - omitted when the note is embedded in an activity
+    #[serde(rename = "@context", skip_serializing_if = "Option::is_none")]
+    pub context: Option<String>,
+
+    pub id: String,
+
+    // Always "Note"
+    #[serde(rename = "type")]
+    pub kind: String,
+
+    // Id of the author's Person object
+    pub attributed_to: String,
+
+    // Tweet text (empty when the text is stored off-chain, see `url`)
+    pub content: String,
+
+    // RFC 3339 timestamp, e.g. "2024-01-31T12:00:00Z"
+    pub published: String,
+
+    // Audience - tweets are always public
+    pub to: Vec<String>,
+
+    // Off-chain location of the text (ipfs:// or ar://), if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+// ActivityPerson is an account shaped as an ActivityStreams "Person" actor
+#[near(serializers = [json])]
+#[derive(Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityPerson {
+    #[serde(rename = "@context")]
+    pub context: String,
+
+    pub id: String,
+
+    // Always "Person"
+    #[serde(rename = "type")]
+    pub kind: String,
+
+    // The NEAR account id (there are no display names or profiles yet)
+    pub preferred_username: String,
+
+    // Profile fields: one per attested external identity (Mastodon shows these as a table)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub attachment: Vec<ActivityPropertyValue>,
+}
+
+// ActivityPropertyValue is a profile field in the schema.org PropertyValue shape Mastodon uses
+#[near(serializers = [json])]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ActivityPropertyValue {
+    // Always "PropertyValue"
+    #[serde(rename = "type")]
+    pub kind: String,
+
+    // Field label, e.g. "GitHub"
+    pub name: String,
+
+    // Field value, e.g. the handle
+    pub value: String,
+}
+
+// ActivityCreate is the "Create" activity announcing a new tweet, wrapping its Note
+#[near(serializers = [json])]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ActivityCreate {
+    #[serde(rename = "@context")]
+    pub context: String,
+
+    pub id: String,
+
+    // Always "Create"
+    #[serde(rename = "type")]
+    pub kind: String,
+
+    // Id of the author's Person object
+    pub actor: String,
+
+    pub published: String,
+
+    pub to: Vec<String>,
+
+    pub object: ActivityNote,
+}
+
+// ArchiveRecord is one tweet of an archive exported from another platform
+// A simplified version of the tweets in a Twitter/X "Download your data" archive
+#[near(serializers = [json])]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArchiveRecord {
+    pub text: String,
+
+    // Original creation time (nanoseconds since Unix epoch, like block timestamps)
+    pub created_at: Timestamp,
+
+    // Id of the tweet this one replied to on the original platform, if any
+    // Validated but not linked: this contract has no reply threading
+    pub in_reply_to: Option<String>,
+}
+
+// TweetPage is one page of a v2 (cursor-paginated) tweet listing
+// Like a REST response with a "next" link: pass next_cursor back to get the following page
+#[near(serializers = [json])]
+#[derive(Clone, Debug, PartialEq)]
+pub struct TweetPage {
+    // Tweets of this page, newest first
+    pub tweets: Vec<Tweet>,
+
+    // Cursor for the next (older) page; None when there are no older tweets
+    pub next_cursor: Option<TweetId>,
+}
+
+// ExportPage is one page of an account's data export (see export_my_data)
+// Like one page of a GDPR "download your data" archive, in a documented JSON format
+#[near(serializers = [json])]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExportPage {
+    // Version of the record shapes (EXPORT_SCHEMA_VERSION)
+    pub schema_version: u32,
+
+    pub section: String,
+
+    // Records of the section, each in the same JSON shape the regular views return
+    pub records: Vec<near_sdk::serde_json::Value>,
+
+    // Cursor for the next page of this section; None when the section is complete
+    pub next_cursor: Option<TweetId>,
+}
+
+// InvariantReport is returned by check_invariants() - like the output of a database
+// consistency check (fsck, CHECK TABLE) after a migration
+#[near(serializers = [json])]
+#[derive(Clone, Debug, PartialEq)]
+pub struct InvariantReport {
+    // How many stored tweets were inspected
+    pub tweets_checked: u32,
+
+    // Where the next call should continue the cleanup queue and draft checks
+    // (None = both were checked to the end)
+    pub next_index: Option<u32>,
+
+    // Human-readable description of every broken invariant (empty = healthy)
+    pub violations: Vec<String>,
+}
+
+// Snapshot is a digest of key state recorded by create_snapshot() at a given block
+// Like a nightly checksum of a database table: auditors compare it against what RPC nodes
+// return today (or returned at that block height) without downloading the whole state
+#[near(serializers = [borsh, json])]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Snapshot {
+    // Position in the snapshot list (0 = oldest)
+    pub id: u32,
+
+    // Block the snapshot was taken in - query archival RPC nodes at this height to compare
+    pub block_height: u64,
+    pub timestamp: Timestamp,
+
+    // Counters at that block
+    pub tweet_count: u32,
+    pub next_tweet_id: u64,
+    pub next_op_id: u64,
+
+    // How many tweet ids went into recent_tweets_hash (at most SNAPSHOT_RECENT_TWEETS)
+    pub recent_tweets: u32,
+
+    // sha256 hash chain over the ids of the most recent live tweets, oldest first:
+    // h = sha256(h || id as 8 little-endian bytes), starting from 32 zero bytes
+    pub recent_tweets_hash: Base64VecU8,
+
+    // Root of the tweet accumulator over the first next_tweet_id tweets (see InclusionProof)
+    pub tweets_root: Base64VecU8,
+}
+
+// InclusionProof is returned by get_inclusion_proof() - proves that a tweet was among the
+// first tree_size tweets ever posted, like a certificate transparency inclusion proof
+//
+// Every posted tweet is a leaf of an append-only Merkle forest (a "Merkle mountain range"):
+// - leaf = sha256(0x00 || id || timestamp || content_hash || author), numbers as 8 LE bytes
+// - node = sha256(0x01 || left || right)
+// - root = sha256(peak || peak || ...), the roots of the perfect subtrees from left to right
+// To verify: hash the leaf up the path, compare with peaks[peak_index], then hash the peaks
+// and compare with a root obtained independently (a snapshot or another RPC provider)
+#[near(serializers = [json])]
+#[derive(Clone, Debug, PartialEq)]
+pub struct InclusionProof {
+    pub tweet_id: TweetId,
+
+    // Number of leaves the root covers (tweet ids 0..tree_size)
+    pub tree_size: u64,
+    pub leaf_hash: Base64VecU8,
+
+    // Sibling hashes from the leaf up to its peak
+    pub path: Vec<ProofStep>,
+
+    // All peaks at tree_size, left to right, and which one the path ends at
+    pub peaks: Vec<Base64VecU8>,
+    pub peak_index: u32,
+    pub root: Base64VecU8,
+}
+
+// One step of an inclusion proof path
+#[near(serializers = [json])]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProofStep {
+    pub sibling: Base64VecU8,
+
+    // Whether the sibling is hashed on the left: node = sha256(0x01 || sibling || current)
+    pub sibling_is_left: bool,
+}
+
+// MethodMetric is one entry of get_method_metrics() - like a per-route request counter
+// in an APM dashboard (requests served by POST /tweets, ...)
+#[near(serializers = [json])]
+#[derive(Clone, Debug, PartialEq)]
+pub struct MethodMetric {
+    pub method: String,
+
+    // "call" or "owner", as in the API catalog
+    pub kind: String,
+
+    // Successful calls since deployment (failed calls roll back their own increment)
+    pub calls: u64,
+}
+
+// Compile-time entry of the API catalog (see API_METHODS)
+struct ApiMethodSpec {
+    name: &'static str,
+    kind: &'static str,
+    args: &'static [&'static str],
+    pagination: Option<&'static str>,
+}
+
+impl ApiMethodSpec {
+    const fn call(name: &'static str, args: &'static [&'static str]) -> Self {
+        Self::new(name, "call", args)
+    }
+
+    const fn owner(name: &'static str, args: &'static [&'static str]) -> Self {
+        Self::new(name, "owner", args)
+    }
+
+    const fn view(name: &'static str, args: &'static [&'static str]) -> Self {
+        Self::new(name, "view", args)
+    }
+
+    const fn new(name: &'static str, kind: &'static str, args: &'static [&'static str]) -> Self {
+        Self {
+            name,
+            kind,
+            args,
+            pagination: None,
+        }
+    }
+
+    const fn paginated(self, style: &'static str) -> Self {
+        Self {
+            pagination: Some(style),
+            ..self
+        }
+    }
+}
+
+// ApiMethod describes one public method in get_api_spec()
+// Similar to one operation of an OpenAPI document
+#[near(serializers = [json])]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ApiMethod {
+    pub name: String,
+
+    // "view" (free read), "call" (transaction) or "owner" (transaction by the contract owner)
+    pub kind: String,
+
+    // Arguments as "name: Type", with "?" marking optional ones (e.g. "limit?: u64")
+    pub args: Vec<String>,
+
+    // "offset" (from_index + limit), "timestamp" or "cursor" for list methods, None otherwise
+    pub pagination: Option<String>,
+}
+
+// ApiSpec is returned by get_api_spec() - like serving an openapi.json for code generators
+#[near(serializers = [json])]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ApiSpec {
+    // Version of the deployed code (same as get_status)
+    pub contract_version: String,
+
+    pub methods: Vec<ApiMethod>,
+
+    // Fixed error messages failed calls can return
+    pub errors: Vec<String>,
+}
+
+// ContractStatus is returned by get_status() for readiness checks
+// Similar to the JSON body of a GET /health or GET /status endpoint
+#[near(serializers = [json])]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContractStatus {
+    // Version of the deployed code (from Cargo.toml), like a build version in /health
+    pub contract_version: String,
+
+    // Version of the stored state layout
+    pub state_schema_version: u32,
+
+    // Current operating mode of the contract
+    pub mode: ContractMode,
+
+    // If the contract is being shut down, when the account may be deleted (None otherwise)
+    pub decommission_after: Option<Timestamp>,
+
+    // Progress of the final shutdown steps, once the grace period is over
+    pub decommission_progress: Option<DecommissionProgress>,
+
+    // SHA-256 of the Borsh-encoded config - lets operators spot config drift across
+    // deployments without comparing every field
+    pub config_digest: Base64VecU8,
+
+    // Maintenance progress: deleted tweets still waiting for gc_indexes
+    pub gc_pending: u32,
+
+    // Next tweet id the expire_tweets sweep looks at (it wraps around to 0 at the end)
+    pub expiry_cursor: u64,
+
+    // Tweets of the previous state layout still waiting for migrate_legacy_tweets
+    pub migration_pending: u64,
+}
+
+// DecommissionProgress tracks the final steps of a shutdown, once the grace period is over
+// Each step runs in bounded batches and can be resumed, like the checkpoints of a teardown job
+#[near(serializers = [borsh, json])]
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecommissionProgress {
+    // Next tweet id to hash into the final digest (the digest is done at next_tweet_id)
+    pub next_digest_id: u64,
+
+    // SHA-256 hash chain over the Borsh-encoded tweets hashed so far, in id order
+    pub digest: Base64VecU8,
+
+    // Storage keys removed by remove_state_keys - once any is gone, the shutdown can't be undone
+    pub keys_removed: u64,
+
+    // An account deletion is in flight (reset by the callback if it fails)
+    pub deletion_pending: bool,
+}
+
+// LegacyTweet is a tweet as stored by the first deployed version of the contract
+// Borsh has no field names or defaults, so old rows can't be read with the current Tweet type -
+// like a table that needs an ALTER TABLE + backfill before the new code can read it
+#[near(serializers = [borsh])]
+struct LegacyTweet {
+    id: u64,
+    author: AccountId,
+    text: String,
+    timestamp: Timestamp,
+    likes: u64,
+}
+
+// State layout of the first deployed version (state schema version 1)
+// migrate() reads it once; its tweets stay under the "t" prefix until they are converted
+#[near(serializers = [borsh])]
+struct LegacyTwitterContract {
+    tweets: IterableMap<u64, LegacyTweet>,
+    next_tweet_id: u64,
+}
+
+// Progress of converting version 1 tweets, like the cursor of a backfill job
+// Ids are converted in order, so the time-ordered index and the accumulator stay id-aligned
+#[near(serializers = [borsh])]
+struct LegacyMigration {
+    tweets: IterableMap<u64, LegacyTweet>,
+
+    // Next legacy tweet id to convert, and the first id the legacy version never assigned
+    next_id: u64,
+    end_id: u64,
+}
+
+// ================================================================================================
+// SMART CONTRACT STATE
+// ================================================================================================
+
+// TwitterContract is the main contract state - think of this as your application's
+// in-memory state that persists between requests (like instance variables in a service class)
+//
+// In traditional backend:
+// - You'd have a database to store tweets
+// - You'd have application state in memory/cache
+//
+// In NEAR:
+// - Contract state IS your database (stored on blockchain)
+// - State persists between function calls
+// - State is automatically loaded/saved by NEAR runtime
+#[near(contract_state)]
+#[derive(PanicOnDefault)] // Prevents accidental initialization without proper setup
+pub struct TwitterContract {
+    // Storage for all tweets - like your main tweets table
+    // IterableMap is NEAR's version of HashMap optimized for blockchain storage
+    // Key: tweet_id, Value: Tweet object
+    tweets: IterableMap<TweetId, Tweet>,
+
+    // Counter for generating unique tweet IDs (like auto-increment in SQL)
+    // This ensures each tweet gets a unique identifier
+    next_tweet_id: u64,
+
+    // Current operating mode (normal vs read-only maintenance)
+    // Like a feature flag stored in your config database
+    mode: ContractMode,
+
+    // Set once the owner announces a shutdown: the time after which the account can be deleted
+    // While this is set, all writes except deletions are frozen
+    decommission_after: Option<Timestamp>,
+
+    // Progress of digest_final_state / remove_state_keys / finalize_decommission
+    decommission_progress: Option<DecommissionProgress>,
+
+    // Owner-tunable settings (like your application's config table)
+    config: Config,
+
+    // Curated topics registry - like a small lookup table of categories
+    // Vector is NEAR's version of an ArrayList: the topic id is its index
+    topics: Vector<Topic>,
+
+    // Inverted index for keyword search: word -> ids of tweets containing it (oldest first)
+    // Like a full-text index in PostgreSQL/Elasticsearch, but maintained by hand at post time
+    // Entries of deleted tweets are skipped when searching rather than removed on delete;
+    // gc_indexes cleans them up later in bounded batches
+    search_index: LookupMap<String, Vec<TweetId>>,
+
+    // Next tweet id the expire_tweets sweep looks at (wraps around to 0 at the end)
+    expiry_cursor: u64,
+
+    // Deleted tweets whose search index entries still have to be removed, with their terms
+    // Like a cleanup job queue: deletes enqueue, gc_indexes drains it a few items at a time
+    gc_queue: Vector<(TweetId, Vec<String>)>,
+
+    // Search terms of off-chain tweets, by tweet id - their text isn't stored, so these are
+    // kept to find their search index entries again when the tweet is deleted
+    offchain_terms: LookupMap<TweetId, Vec<String>>,
+
+    // Activity counters per day (key: days since Unix epoch)
+    daily_stats: LookupMap<u32, DailyStats>,
+
+    // Engagement per author and week - like a creator analytics rollup table
+    weekly_rollups: LookupMap<(AccountId, u32), WeeklyRollup>,
+
+    // Engagement per persona handle and week - the per-persona breakdown of weekly_rollups,
+    // where the same posts and likes also count for the persona's owner
+    persona_rollups: LookupMap<(String, u32), WeeklyRollup>,
+
+    // Every account that has ever posted or liked - used to count new accounts
+    // LookupSet is like a HashSet: membership checks only, no iteration
+    known_accounts: LookupSet<AccountId>,
+
+    // Co-authored tweets waiting for approvals - like a pending drafts table
+    drafts: IterableMap<u64, Draft>,
+
+    // Counter for generating unique draft IDs
+    next_draft_id: u64,
+
+    // Posting delegations granted by each account (active and revoked)
+    // Like a user_delegates table keyed by the granting user
+    delegations: LookupMap<AccountId, Vec<Delegation>>,
+
+    // Personas (brand accounts) by handle - like an organizations table
+    personas: LookupMap<String, Persona>,
+
+    // Handles of the personas each account owns, in creation order (for export_my_data)
+    // Like a secondary index on organizations.owner_id
+    personas_by_owner: LookupMap<AccountId, Vec<String>>,
+
+    // Posts received from federation peers, in arrival order
+    federated_posts: Vector<FederatedPost>,
+
+    // Listener contracts and the event mask each one subscribed with
+    // Like a webhook subscriptions table: endpoint -> event filter
+    listeners: IterableMap<AccountId, u32>,
+
+    // Recent user writes by op id (the last OP_LOG_RETENTION entries)
+    // Like an append-only audit log table with a retention policy
+    operations: LookupMap<u64, Operation>,
+
+    // Counter for generating operation ids
+    next_op_id: u64,
+
+    // Idempotency keys of retained operations: (account, key) -> op id
+    // Like a unique index on (user_id, idempotency_key) in a payments API
+    idempotency_keys: LookupMap<(AccountId, String), u64>,
+
+    // Block height of the last emitted event and the index the next event in that block gets
+    // Lets indexers totally order events without joining against chain data
+    last_event_block_height: u64,
+    next_event_index: u32,
+
+    // Time-ordered index: post timestamp of every tweet ever posted, indexed by tweet id
+    // Ids are assigned in posting order and block timestamps never decrease, so this is sorted
+    // and can be binary searched (like a B-tree index on created_at). Deleted tweets keep
+    // their entry, which keeps timestamp cursors stable under deletions
+    tweet_timestamps: Vector<Timestamp>,
+
+    // Per-account settings - like a user_settings table keyed by user id
+    settings: LookupMap<AccountId, AccountSettings>,
+
+    // Short link registry: code -> destination (like a URL shortener's links table)
+    short_links: LookupMap<String, ShortLink>,
+
+    // Codes of the short links each account registered, in registration order
+    short_links_by_owner: LookupMap<AccountId, Vec<String>>,
+
+    // Attested external identities per account (at most one per platform)
+    identities: LookupMap<AccountId, Vec<LinkedIdentity>>,
+
+    // Spaces that haven't ended yet (live or upcoming) - kept small so listing them is cheap
+    live_spaces: IterableMap<u64, Space>,
+
+    // Spaces that have ended, kept for lookups by id (like an archive table)
+    ended_spaces: LookupMap<u64, Space>,
+
+    // Counter for generating unique space IDs
+    next_space_id: u64,
+
+    // Structured details of event tweets, by tweet id
+    event_details: LookupMap<TweetId, EventDetails>,
+
+    // Each account's answer to an event - like a (event_id, account_id) primary key
+    // One small entry per answer, so answering never rewrites the whole attendee list
+    rsvps: LookupMap<(TweetId, AccountId), Rsvp>,
+
+    // Attendees of each event in the order they first answered, for paging through them
+    event_attendees: LookupMap<(TweetId, u32), AccountId>,
+
+    // Counter the short link codes are generated from
+    next_link_id: u64,
+
+    // State snapshots taken by the owner, oldest first (append-only, like an audit table)
+    snapshots: Vector<Snapshot>,
+
+    // Nodes of the tweet accumulator: (height, index) -> hash, height 0 being the leaves
+    // Nodes never change once written, so proofs against older roots stay possible
+    tweet_tree: LookupMap<(u8, u64), [u8; 32]>,
+
+    // Rate limiter: one fixed-size token bucket per account that has written while
+    // rate limiting was enabled, plus counters for get_rate_limit_metrics
+    rate_buckets: LookupMap<AccountId, RateBucket>,
+    rate_limited_accounts: u64,
+    metered_writes: u64,
+
+    // Usage telemetry: successful calls per write method name (views can't write state)
+    method_calls: LookupMap<String, u64>,
+
+    // Set by migrate() while version 1 tweets are being converted (None once done)
+    legacy_migration: Option<LegacyMigration>,
+
+    // Recent likes per tweet in a ring of RISING_BUCKETS buckets (key: bucket % RISING_BUCKETS)
+    // Like a sliding-window counter in Redis: old buckets are overwritten, never deleted
+    like_buckets: LookupMap<u64, LikeBucket>,
+
+    // M-of-N admin council; None while the contract account's key calls owner methods alone
+    admin_council: Option<AdminCouncil>,
+
+    // Admin proposals by id, executed ones included - like a change request table
+    admin_proposals: LookupMap<u64, AdminProposal>,
+
+    // Counter for generating admin proposal ids
+    next_admin_proposal_id: u64,
+}
+
+// ================================================================================================
+// CONTRACT IMPLEMENTATION
+// ================================================================================================
+
+// The #[near] attribute marks this implementation block as containing contract methods
+// Think of these as your REST API endpoints, but they run on the blockchain
+#[near]
+impl TwitterContract {
+    // ============================================================================================
+    // INITIALIZATION METHOD
+    // ============================================================================================
+
+    // Contract constructor - called once when the contract is first deployed
+    // Similar to database migrations or initial setup in traditional backends
+    // The #[init] attribute marks this as the initialization method
+    #[init]
+    pub fn new() -> Self {
+        Self {
+            // Initialize tweet storage with a unique storage prefix
+            // "b'T'" is a byte string prefix to avoid storage conflicts
+            // Think of this as creating a table in your database
+            // ("t" holds the tweets of the first version until migrate_legacy_tweets moves them)
+            tweets: IterableMap::new(b"T"),
+
+            // Start tweet IDs from 0
+            next_tweet_id: 0,
+
+            // Contract starts fully operational
+            mode: ContractMode::Active,
+
+            // No shutdown scheduled
+            decommission_after: None,
+            decommission_progress: None,
+
+            // Default settings: hybrid storage disabled
+            config: Config::default(),
+
+            // Topics registry starts empty, the owner creates topics later
+            topics: Vector::new(b"p"),
+
+            // Keyword search index starts empty
+            search_index: LookupMap::new(b"w"),
+            gc_queue: Vector::new(b"q"),
+            offchain_terms: LookupMap::new(b"O"),
+            expiry_cursor: 0,
+
+            // Activity tracking starts empty
+            daily_stats: LookupMap::new(b"d"),
+            weekly_rollups: LookupMap::new(b"y"),
+            persona_rollups: LookupMap::new(b"W"),
+            known_accounts: LookupSet::new(b"a"),
+
+            // No co-authored tweets proposed yet
+            drafts: IterableMap::new(b"r"),
+            next_draft_id: 0,
+
+            // No posting delegations granted yet
+            delegations: LookupMap::new(b"g"),
+
+            // No personas created yet
+            personas: LookupMap::new(b"j"),
+            personas_by_owner: LookupMap::new(b"P"),
+
+            // No posts received from other communities yet
+            federated_posts: Vector::new(b"f"),
+
+            // No listener contracts registered yet
+            listeners: IterableMap::new(b"l"),
+
+            // Operation log starts empty
+            operations: LookupMap::new(b"o"),
+            next_op_id: 0,
+            idempotency_keys: LookupMap::new(b"k"),
+
+            // No events emitted yet
+            last_event_block_height: 0,
+            next_event_index: 0,
+
+            // Time-ordered index starts empty
+            tweet_timestamps: Vector::new(b"s"),
+
+            // No account has changed its settings yet
+            settings: LookupMap::new(b"u"),
+
+            // No short links yet
+            short_links: LookupMap::new(b"h"),
+            short_links_by_owner: LookupMap::new(b"L"),
+            next_link_id: 0,
+
+            // No linked identities yet
+            identities: LookupMap::new(b"i"),
+
+            // No spaces yet
+            live_spaces: IterableMap::new(b"v"),
+            ended_spaces: LookupMap::new(b"e"),
+            next_space_id: 0,
+
+            // No event tweets yet
+            event_details: LookupMap::new(b"x"),
+            rsvps: LookupMap::new(b"R"),
+            event_attendees: LookupMap::new(b"A"),
+
+            // No snapshots taken yet
+            snapshots: Vector::new(b"n"),
+
+            // Tweet accumulator starts empty
+            tweet_tree: LookupMap::new(b"m"),
+
+            // No account has been rate limited yet
+            rate_buckets: LookupMap::new(b"b"),
+            rate_limited_accounts: 0,
+            metered_writes: 0,
+
+            // No method has been called yet
+            method_calls: LookupMap::new(b"c"),
+            like_buckets: LookupMap::new(b"z"),
+
+            // Fresh deployments have nothing to migrate
+            legacy_migration: None,
+
+            // No admin council until the owner sets one up
+            admin_council: None,
+            admin_proposals: LookupMap::new(b"C"),
+            next_admin_proposal_id: 0,
+        }
+    }
+
+    // Upgrade state written by the first deployed version - like running a schema migration
+    // after deploying new code. Deploy the new code without an init call, then call this once
+    // (it has to be signed by the contract account) and run migrate_legacy_tweets until
+    // get_status reports migration_pending: 0
+    // ignore_state: this runs instead of loading the state, which no longer matches the code
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        let legacy: LegacyTwitterContract =
+            env::state_read().unwrap_or_else(|| env::panic_str("No contract state to migrate"));
+
+        // Everything added since version 1 starts empty, exactly as in a fresh deployment
+        let mut contract = Self::new();
+        contract.count_call("migrate");
+
+        // Read-only until every legacy tweet is converted: new tweets would take ids the
+        // legacy tweets still need (set_mode can't switch back before that)
+        contract.mode = ContractMode::ReadOnly;
+        env::log_str(&format!(
+            "State migrated to schema version {}: {} legacy tweet ids to convert",
+            STATE_SCHEMA_VERSION, legacy.next_tweet_id
+        ));
+        contract.legacy_migration = Some(LegacyMigration {
+            tweets: legacy.tweets,
+            next_id: 0,
+            end_id: legacy.next_tweet_id,
+        });
+        contract
+    }
+
+    // Convert up to `limit` legacy tweets to the current layout - like a batched backfill job
+    // Each tweet keeps its id, author, text, timestamp and likes, and is indexed for search and
+    // added to the accumulator like a new post. Ids deleted before the upgrade stay unused.
+    // Once the last one is converted, the contract switches back to Active mode.
+    // Returns how many tweet ids were processed
+    pub fn migrate_legacy_tweets(&mut self, limit: u32) -> u32 {
+        self.count_call("migrate_legacy_tweets");
+
+        self.assert_owner();
+        let Some(mut migration) = self.legacy_migration.take() else {
+            env::panic_str("No legacy tweets to migrate");
+        };
+
+        let mut processed = 0;
+        while processed < limit.min(MAX_MIGRATION_BATCH) && migration.next_id < migration.end_id {
+            let tweet_id = TweetId(migration.next_id);
+            match migration.tweets.remove(&migration.next_id) {
+                Some(legacy) => {
+                    let tweet = Tweet {
+                        id: tweet_id,
+                        author: legacy.author,
+                        content_hash: content_hash(&legacy.text),
+                        text: legacy.text,
+                        timestamp: legacy.timestamp,
+                        likes: legacy.likes,
+                        license: None,
+                        storage_tier: StorageTier::OnChain,
+                        topics: Vec::new(),
+                        coauthors: Vec::new(),
+                        signer: None,
+                        imported: false,
+                        original_created_at: None,
+                        frozen: false,
+                        adult: false,
+                        persona: None,
+                    };
+                    self.tweet_timestamps.push(tweet.timestamp);
+                    self.append_tree_leaf(tweet_id, tweet_leaf_hash(&tweet));
+                    self.index_terms(tweet_id, tokenize(&tweet.text));
+                    self.known_accounts.insert(tweet.author.clone());
+                    self.tweets.insert(tweet_id, tweet);
+                }
+                None => {
+                    // Deleted before the upgrade: keep the indexes aligned with tweet ids
+                    // (the previous timestamp keeps the time-ordered index sorted)
+                    let timestamp = self
+                        .tweet_timestamps
+                        .len()
+                        .checked_sub(1)
+                        .map_or(0, |last| self.tweet_timestamps[last]);
+                    self.tweet_timestamps.push(timestamp);
+                    self.append_tree_leaf(tweet_id, [0u8; 32]);
+                }
+            }
+            migration.next_id += 1;
+            self.next_tweet_id = migration.next_id;
+            processed += 1;
+        }
+
+        if migration.next_id < migration.end_id {
+            self.legacy_migration = Some(migration);
+        } else {
+            self.mode = ContractMode::Active;
+            env::log_str("Legacy tweet migration complete, contract is active again");
+        }
+        processed
+    }
+
+    // ============================================================================================
+    // WRITE METHODS (Cost gas, modify state)
+    // ============================================================================================
+
+    // Post a new tweet - equivalent to POST /tweets endpoint
+    // This is a "call" method that modifies state and costs gas
+    // license: optional re-use license for the content (like an optional field in the request body)
+    // content_uri: ipfs:// or ar:// pointer, required when the text exceeds max_onchain_bytes
+    // topics: ids of curated topics to tag the tweet with (at most MAX_TOPICS_PER_TWEET)
+    // idempotency_key: client-chosen unique key; retrying with the same key returns the
+    // originally posted tweet instead of posting a duplicate (like the Idempotency-Key header)
+    pub fn post_tweet(
+        &mut self,
+        text: String,
+        license: Option<License>,
+        content_uri: Option<String>,
+        topics: Option<Vec<u32>>,
+        idempotency_key: Option<String>,
+    ) -> WithOpId<Tweet> {
+        self.count_call("post_tweet");
+
+        // Reject new content during maintenance (like returning 503 on POST during a migration)
+        self.assert_content_creation_allowed();
+
+        // A wallet or relayer retry of a write that already landed: return the original result
+        if let Some(op) = self.find_replay(OperationKind::PostTweet, &idempotency_key) {
+            return self.replayed_tweet(&op);
+        }
+
+        // Validate topic tags (like checking foreign keys before an INSERT)
+        let topics = self.validate_topics(topics.unwrap_or_default());
+
+        // Get the account that called this method (like extracting user from JWT token)
+        // env::predecessor_account_id() returns who made the transaction
+        let author = env::predecessor_account_id();
+
+        // Retries above don't count against the rate limit, new posts do
+        self.consume_rate_limit(&author, 1);
+
+        let tweet = self.insert_tweet(
+            author,
+            Vec::new(),
+            None,
+            text,
+            license,
+            content_uri,
+            topics,
+            None,
+        );
+        let op_id = self.record_op(
+            OperationKind::PostTweet,
+            Some(OpTarget::Tweet(tweet.id)),
+            idempotency_key,
+        );
+        WithOpId {
+            op_id,
+            value: tweet,
+        }
+    }
+
+    // Post a tweet on behalf of another account - like POST /users/{id}/tweets as a team member
+    // The caller must be an active posting delegate of `author` (see grant_posting_delegate);
+    // the tweet's byline is `author` and the caller is recorded as its `signer`
+    pub fn post_tweet_on_behalf(
+        &mut self,
+        author: AccountId,
+        text: String,
+        license: Option<License>,
+        content_uri: Option<String>,
+        topics: Option<Vec<u32>>,
+        idempotency_key: Option<String>,
+    ) -> WithOpId<Tweet> {
+        self.count_call("post_tweet_on_behalf");
+
+        self.assert_content_creation_allowed();
+
+        if let Some(op) = self.find_replay(OperationKind::PostTweet, &idempotency_key) {
+            return self.replayed_tweet(&op);
+        }
+
+        let signer = env::predecessor_account_id();
+        if !self.is_posting_delegate(&author, &signer) {
+            env::panic_str("Caller is not a posting delegate of this author");
+        }
+
+        // Delegates share the author's budget, so adding delegates can't multiply it
+        self.consume_rate_limit(&author, 1);
+
+        let topics = self.validate_topics(topics.unwrap_or_default());
+
+        let tweet = self.insert_tweet(
+            author,
+            Vec::new(),
+            Some(signer),
+            text,
+            license,
+            content_uri,
+            topics,
+            None,
+        );
+        let op_id = self.record_op(
+            OperationKind::PostTweet,
+            Some(OpTarget::Tweet(tweet.id)),
+            idempotency_key,
+        );
+        WithOpId {
+            op_id,
+            value: tweet,
+        }
+    }
+
+    // Like a tweet - equivalent to POST /tweets/{id}/like endpoint
+    // This modifies state (increments like counter) so it costs gas
+    // idempotency_key: retrying with the same key doesn't count the like twice
+    pub fn like_tweet(
+        &mut self,
+        tweet_id: TweetId,
+        idempotency_key: Option<String>,
+    ) -> Option<WithOpId<Tweet>> {
+        self.count_call("like_tweet");
+
+        // Likes are writes too, so they are frozen while the contract shuts down
+        self.assert_not_decommissioning();
+
+        if let Some(op) = self.find_replay(OperationKind::LikeTweet, &idempotency_key) {
+            let tweet = match op.target {
+                Some(OpTarget::Tweet(tweet_id)) => self.tweets.get(&tweet_id).cloned(),
+                _ => None,
+            };
+            return tweet.map(|tweet| WithOpId {
+                op_id: op.op_id,
+                value: tweet,
+            });
+        }
+
+        // Only likes of existing tweets use up the rate limit
+        if self.tweets.contains_key(&tweet_id) {
+            self.consume_rate_limit(&env::predecessor_account_id(), 1);
+        }
+
+        // Try to get a mutable reference to the tweet
+        // This is like: SELECT * FROM tweets WHERE id = ? FOR UPDATE
+        if let Some(tweet) = self.tweets.get_mut(&tweet_id) {
+            // Increment the like counter (like UPDATE tweets SET likes = likes + 1)
+            tweet.likes += 1;
+
+            // Log the like action for transparency/debugging
+            env::log_str(&format!(
+                "Tweet #{} liked by @{}. Total likes: {}",
+                tweet_id,
+                env::predecessor_account_id(), // Who liked the tweet
+                tweet.likes
+            ));
+
+            // Clone because we need to return owned data
+            let liked_tweet = tweet.clone();
+
+            // Count the like in today's stats and the weekly rollups of the author and persona
+            let author = liked_tweet.author.clone();
+            self.track_account(&env::predecessor_account_id());
+            self.todays_stats().likes += 1;
+            self.this_weeks_rollup(&author).likes_received += 1;
+            if let Some(handle) = &liked_tweet.persona {
+                self.this_weeks_persona_rollup(handle).likes_received += 1;
+            }
+            self.count_recent_like(tweet_id);
+
+            self.emit_event(
+                TwitterEvent::TweetLiked,
+                tweet_id,
+                &env::predecessor_account_id(),
+            );
+            let op_id = self.record_op(
+                OperationKind::LikeTweet,
+                Some(OpTarget::Tweet(tweet_id)),
+                idempotency_key,
+            );
+
+            // Return the updated tweet
+            Some(WithOpId {
+                op_id,
+                value: liked_tweet,
+            })
+        } else {
+            // Tweet doesn't exist - log the attempt
+            // In REST API, this would be a 404 Not Found
+            env::log_str(&format!(
+                "Attempt to like non-existent tweet #{} by @{}",
+                tweet_id,
+                env::predecessor_account_id()
+            ));
+            None
+        }
+    }
+
+    // Propose a co-authored tweet - like POST /drafts with a list of reviewers
+    // The tweet is only published once every listed co-author calls approve_coauthorship
+    pub fn propose_coauthored_tweet(&mut self, text: String, coauthors: Vec<AccountId>) -> Draft {
+        self.count_call("propose_coauthored_tweet");
+
+        self.assert_content_creation_allowed();
+
+        let author = env::predecessor_account_id();
+        self.consume_rate_limit(&author, 1);
+
+        // Normalize the co-author list: no duplicates, and the proposer is implicitly an author
+        let mut coauthors = coauthors;
+        coauthors.sort_unstable();
+        coauthors.dedup();
+        coauthors.retain(|coauthor| *coauthor != author);
+
+        if coauthors.is_empty() {
+            env::panic_str("A co-authored tweet needs at least one co-author besides the proposer");
+        }
+        if coauthors.len() > MAX_COAUTHORS {
+            env::panic_str(&format!(
+                "Too many co-authors: a tweet can have at most {} co-authors",
+                MAX_COAUTHORS
+            ));
+        }
+
+        // Co-authored tweets are always stored on-chain, so reject text that would need a content_uri
+        if let Some(max_bytes) = self.config.max_onchain_bytes {
+            if text.len() > max_bytes as usize {
+                env::panic_str("Co-authored tweets must fit within max_onchain_bytes");
+            }
+        }
+
+        let draft = Draft {
+            id: self.next_draft_id,
+            author: author.clone(),
+            text,
+            coauthors,
+            approvals: Vec::new(),
+            created_at: env::block_timestamp(),
+            expires_at: env::block_timestamp() + DRAFT_TTL,
+        };
+        self.drafts.insert(draft.id, draft.clone());
+        self.next_draft_id += 1;
+        self.record_op(
+            OperationKind::ProposeDraft,
+            Some(OpTarget::Draft(draft.id)),
+            None,
+        );
+
+        env::log_str(&format!(
+            "Draft #{} proposed by @{}, waiting for {} co-author approvals",
+            draft.id,
+            author,
+            draft.coauthors.len()
+        ));
+
+        draft
+    }
+
+    // Approve a co-authored draft - like POST /drafts/{id}/approve
+    // Returns the approval's op id, and the published tweet once the last co-author approves
+    // (no tweet while approvals are pending)
+    // If max_onchain_bytes was lowered below the draft's size since it was proposed, the draft
+    // can never be published, so it is discarded instead and the returned op id is the discard's
+    pub fn approve_coauthorship(&mut self, draft_id: u64) -> WithOpId<Option<Tweet>> {
+        self.count_call("approve_coauthorship");
+
+        self.assert_content_creation_allowed();
+
+        let caller = env::predecessor_account_id();
+        self.consume_rate_limit(&caller, 1);
+        let max_onchain_bytes = self.config.max_onchain_bytes;
+        let draft = self
+            .drafts
+            .get_mut(&draft_id)
+            .unwrap_or_else(|| env::panic_str("Draft not found"));
+
+        // Only listed co-authors can approve, and only once (like a 403 / 409 in REST)
+        if !draft.coauthors.contains(&caller) {
+            env::panic_str("Only a listed co-author can approve this draft");
+        }
+        if draft.approvals.contains(&caller) {
+            env::panic_str("Co-authorship already approved");
+        }
+        if env::block_timestamp() >= draft.expires_at {
+            env::panic_str("Draft has expired");
+        }
+
+        // Re-check the size limit: the owner may have lowered it while the draft was pending
+        if max_onchain_bytes.is_some_and(|max_bytes| draft.text.len() > max_bytes as usize) {
+            self.drafts.remove(&draft_id);
+            env::log_str(&format!(
+                "Draft #{} discarded: it no longer fits within max_onchain_bytes",
+                draft_id
+            ));
+            let op_id = self.record_op(
+                OperationKind::DiscardDraft,
+                Some(OpTarget::Draft(draft_id)),
+                None,
+            );
+            return WithOpId { op_id, value: None };
+        }
+        draft.approvals.push(caller.clone());
+
+        env::log_str(&format!(
+            "Draft #{} approved by @{} ({}/{})",
+            draft_id,
+            caller,
+            draft.approvals.len(),
+            draft.coauthors.len()
+        ));
+
+        let approved = draft.approvals.len() == draft.coauthors.len();
+        let op_id = self.record_op(
+            OperationKind::ApproveDraft,
+            Some(OpTarget::Draft(draft_id)),
+            None,
+        );
+        if !approved {
+            return WithOpId { op_id, value: None };
+        }
+
+        // Everyone approved: move the draft into the tweets table
+        let draft = self.drafts.remove(&draft_id).expect("Draft exists");
+        let tweet = self.insert_tweet(
+            draft.author,
+            draft.coauthors,
+            None,
+            draft.text,
+            None,
+            None,
+            Vec::new(),
+            None,
+        );
+        self.record_op_for(
+            tweet.author.clone(),
+            OperationKind::PostTweet,
+            Some(OpTarget::Tweet(tweet.id)),
+            None,
+        );
+        WithOpId {
+            op_id,
+            value: Some(tweet),
+        }
+    }
+
+    // Decline a co-authored draft - like POST /drafts/{id}/reject
+    // A single rejection removes the draft, since it can never collect every approval
+    // Returns the op id of the write (see get_op)
+    pub fn reject_coauthorship(&mut self, draft_id: u64) -> u64 {
+        self.count_call("reject_coauthorship");
+
+        self.assert_not_decommissioning();
+
+        let caller = env::predecessor_account_id();
+        let draft = self
+            .drafts
+            .get(&draft_id)
+            .unwrap_or_else(|| env::panic_str("Draft not found"));
+        if !draft.coauthors.contains(&caller) {
+            env::panic_str("Only a listed co-author can reject this draft");
+        }
+        self.drafts.remove(&draft_id);
+
+        env::log_str(&format!("Draft #{} rejected by @{}", draft_id, caller));
+        self.record_op(
+            OperationKind::RejectDraft,
+            Some(OpTarget::Draft(draft_id)),
+            None,
+        )
+    }
+
+    // Withdraw a co-authored draft - like DELETE /drafts/{id}
+    // The proposer can cancel at any time; once the draft has expired anyone can clean it up
+    // Returns the op id of the write (see get_op)
+    pub fn cancel_draft(&mut self, draft_id: u64) -> u64 {
+        self.count_call("cancel_draft");
+
+        let caller = env::predecessor_account_id();
+        let draft = self
+            .drafts
+            .get(&draft_id)
+            .unwrap_or_else(|| env::panic_str("Draft not found"));
+        if draft.author != caller && env::block_timestamp() < draft.expires_at {
+            env::panic_str("Only the proposer can cancel a draft before it expires");
+        }
+        self.drafts.remove(&draft_id);
+
+        env::log_str(&format!("Draft #{} cancelled by @{}", draft_id, caller));
+        self.record_op(
+            OperationKind::CancelDraft,
+            Some(OpTarget::Draft(draft_id)),
+            None,
+        )
+    }
+
+    // Allow another account to post on your behalf - like adding a team member to a brand account
+    // The delegate's tweets show you as the author and them as the signer
+    // Returns the op id of the write (see get_op)
+    pub fn grant_posting_delegate(&mut self, delegate: AccountId) -> u64 {
+        self.count_call("grant_posting_delegate");
+
+        self.assert_not_decommissioning();
+
+        let author = env::predecessor_account_id();
+        if delegate == author {
+            env::panic_str("Cannot delegate posting rights to yourself");
+        }
+        if self.is_posting_delegate(&author, &delegate) {
+            env::panic_str("Account is already a posting delegate");
+        }
+
+        self.delegations
+            .entry(author.clone())
+            .or_default()
+            .push(Delegation {
+                delegate: delegate.clone(),
+                granted_at: env::block_timestamp(),
+                revoked_at: None,
+            });
+
+        env::log_str(&format!(
+            "@{} granted posting rights to @{}",
+            author, delegate
+        ));
+        self.record_op(OperationKind::GrantPostingDelegate, None, None)
+    }
+
+    // Take posting rights away from a delegate - the delegation stays on record as revoked
+    // Allowed even while decommissioning, since it only removes permissions
+    pub fn revoke_posting_delegate(&mut self, delegate: AccountId) -> u64 {
+        self.count_call("revoke_posting_delegate");
+
+        let author = env::predecessor_account_id();
+
+        let active = self
+            .delegations
+            .get_mut(&author)
+            .and_then(|delegations| {
+                delegations.iter_mut().find(|delegation| {
+                    delegation.delegate == delegate && delegation.revoked_at.is_none()
+                })
+            })
+            .unwrap_or_else(|| env::panic_str("Account is not a posting delegate"));
+        active.revoked_at = Some(env::block_timestamp());
+
+        env::log_str(&format!(
+            "@{} revoked posting rights of @{}",
+            author, delegate
+        ));
+        self.record_op(OperationKind::RevokePostingDelegate, None, None)
+    }
+
+    // Create a persona (brand account) owned by the caller - like POST /orgs
+    // Handles are unique and can't be transferred or reused
+    pub fn create_persona(&mut self, handle: String) -> Persona {
+        self.count_call("create_persona");
+
+        self.assert_content_creation_allowed();
+        if handle.is_empty()
+            || handle.len() > MAX_PERSONA_HANDLE_LEN
+            || !handle
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        {
+            env::panic_str("Invalid persona handle");
+        }
+        if self.personas.contains_key(&handle) {
+            env::panic_str("Persona handle is already taken");
+        }
+        self.consume_rate_limit(&env::predecessor_account_id(), 1);
+
+        let persona = Persona {
+            handle: handle.clone(),
+            owner: env::predecessor_account_id(),
+            created_at: env::block_timestamp(),
+            managers: Vec::new(),
+        };
+        self.personas.insert(handle.clone(), persona.clone());
+        self.personas_by_owner
+            .entry(persona.owner.clone())
+            .or_default()
+            .push(handle.clone());
+        env::log_str(&format!("Persona {} created by @{}", handle, persona.owner));
+        self.record_op(
+            OperationKind::CreatePersona,
+            Some(OpTarget::Persona(handle)),
+            None,
+        );
+        persona
+    }
+
+    // Let another account post as one of your personas - like adding an org member
+    pub fn add_persona_manager(&mut self, handle: String, manager: AccountId) -> Persona {
+        self.count_call("add_persona_manager");
+
+        self.assert_not_decommissioning();
+        let persona = self.assert_persona_owner(&handle);
+        let active = persona
+            .managers
+            .iter()
+            .filter(|grant| grant.revoked_at.is_none())
+            .collect::<Vec<_>>();
+        if manager == persona.owner || active.iter().any(|grant| grant.delegate == manager) {
+            env::panic_str("Account is already a persona manager");
+        }
+        if active.len() >= MAX_PERSONA_MANAGERS {
+            env::panic_str("Persona has too many managers");
+        }
+
+        persona.managers.push(Delegation {
+            delegate: manager.clone(),
+            granted_at: env::block_timestamp(),
+            revoked_at: None,
+        });
+        let persona = persona.clone();
+        env::log_str(&format!("@{} can now post as {}", manager, handle));
+        self.record_op(
+            OperationKind::AddPersonaManager,
+            Some(OpTarget::Persona(handle)),
+            None,
+        );
+        persona
+    }
+
+    // Take a manager's rights away - the grant stays on record as revoked
+    // Allowed even while decommissioning, since it only removes permissions
+    pub fn remove_persona_manager(&mut self, handle: String, manager: AccountId) -> Persona {
+        self.count_call("remove_persona_manager");
+
+        let persona = self.assert_persona_owner(&handle);
+        let grant = persona
+            .managers
+            .iter_mut()
+            .find(|grant| grant.delegate == manager && grant.revoked_at.is_none())
+            .unwrap_or_else(|| env::panic_str("Account is not a persona manager"));
+        grant.revoked_at = Some(env::block_timestamp());
+
+        let persona = persona.clone();
+        env::log_str(&format!("@{} can no longer post as {}", manager, handle));
+        self.record_op(
+            OperationKind::RemovePersonaManager,
+            Some(OpTarget::Persona(handle)),
+            None,
+        );
+        persona
+    }
+
+    // Post a tweet as a persona - like POST /orgs/{handle}/tweets
+    // The caller must be the persona's owner or an active manager. The tweet's author is the
+    // owner, `persona` is the handle, and `signer` records a manager who posted it
+    pub fn post_as_persona(
+        &mut self,
+        handle: String,
+        text: String,
+        license: Option<License>,
+        content_uri: Option<String>,
+        topics: Option<Vec<u32>>,
+        idempotency_key: Option<String>,
+    ) -> WithOpId<Tweet> {
+        self.count_call("post_as_persona");
+
+        self.assert_content_creation_allowed();
+
+        if let Some(op) = self.find_replay(OperationKind::PostTweet, &idempotency_key) {
+            return self.replayed_tweet(&op);
+        }
+
+        let caller = env::predecessor_account_id();
+        let persona = self
+            .personas
+            .get(&handle)
+            .unwrap_or_else(|| env::panic_str("Persona not found"));
+        let owner = persona.owner.clone();
+        let is_manager = persona
+            .managers
+            .iter()
+            .any(|grant| grant.delegate == caller && grant.revoked_at.is_none());
+        if caller != owner && !is_manager {
+            env::panic_str("Caller can't post as this persona");
+        }
+
+        let topics = self.validate_topics(topics.unwrap_or_default());
+
+        // Managers share the owner's rate limit, like posting delegates share the author's
+        self.consume_rate_limit(&owner, 1);
+
+        let signer = (caller != owner).then_some(caller);
+        let tweet = self.insert_tweet(
+            owner,
+            Vec::new(),
+            signer,
+            text,
+            license,
+            content_uri,
+            topics,
+            None,
+        );
+
+        // Attribute the stored tweet to the persona
+        let tweet = self
+            .tweets
+            .get_mut(&tweet.id)
+            .expect("Tweet was just stored");
+        tweet.persona = Some(handle.clone());
+        let tweet = tweet.clone();
+        self.this_weeks_persona_rollup(&handle).posts += 1;
+        let op_id = self.record_op(
+            OperationKind::PostTweet,
+            Some(OpTarget::Tweet(tweet.id)),
+            idempotency_key,
+        );
+        WithOpId {
+            op_id,
+            value: tweet,
+        }
+    }
+
+    // Update the caller's settings - like PATCH /users/me/settings
+    // Returns the settings after applying the patch
+    pub fn update_settings(&mut self, patch: SettingsPatch) -> AccountSettings {
+        self.count_call("update_settings");
+
+        self.assert_not_decommissioning();
+
+        let account_id = env::predecessor_account_id();
+        let settings = self.settings.entry(account_id.clone()).or_default();
+        if patch.clear_default_license && patch.default_license.is_some() {
+            env::panic_str("Pass either default_license or clear_default_license, not both");
+        }
+        if let Some(license) = patch.default_license {
+            settings.default_license = Some(license);
+        }
+        if patch.clear_default_license {
+            settings.default_license = None;
+        }
+        if let Some(language) = patch.language {
+            let valid = language.len() <= MAX_LANGUAGE_TAG_LEN
+                && language.split('-').all(|part| {
+                    !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric())
+                });
+            if !language.is_empty() && !valid {
+                env::panic_str("Invalid language tag");
+            }
+            settings.language = (!language.is_empty()).then_some(language);
+        }
+        if let Some(show_sensitive_content) = patch.show_sensitive_content {
+            settings.show_sensitive_content = show_sensitive_content;
+        }
+        if let Some(days) = patch.expire_after_days {
+            settings.expire_after_days = (days > 0).then_some(days);
+        }
+
+        let settings = settings.clone();
+        env::log_str(&format!("@{} updated their settings", account_id));
+        self.record_op(OperationKind::UpdateSettings, None, None);
+        settings
+    }
+
+    // Recreate tweets from an archive of another platform - like a bulk import endpoint
+    // Imports into the caller's account; the owner may import into any account (e.g. to run a
+    // migration for users). Archives are imported in chunks of at most MAX_IMPORT_CHUNK records;
+    // every record is validated before any is stored, so a chunk is imported entirely or not at all
+    // Returns the ids of the created tweets, in record order
+    pub fn import_archive_chunk(
+        &mut self,
+        records: Vec<ArchiveRecord>,
+        account_id: Option<AccountId>,
+    ) -> Vec<TweetId> {
+        self.count_call("import_archive_chunk");
+
+        self.assert_content_creation_allowed();
+
+        let caller = env::predecessor_account_id();
+        let author = account_id.unwrap_or_else(|| caller.clone());
+        if author != caller {
+            self.assert_owner();
+        }
+        if records.is_empty() || records.len() > MAX_IMPORT_CHUNK {
+            env::panic_str(&format!(
+                "An archive chunk must have between 1 and {} records",
+                MAX_IMPORT_CHUNK
+            ));
+        }
+        for (index, record) in records.iter().enumerate() {
+            if record.created_at == 0 || record.created_at > env::block_timestamp() {
+                env::panic_str(&format!("Record {}: created_at must be in the past", index));
+            }
+            if let Some(max_bytes) = self.config.max_onchain_bytes {
+                if record.text.len() > max_bytes as usize {
+                    env::panic_str(&format!("Record {}: text exceeds max_onchain_bytes", index));
+                }
+            }
+            let valid_reply_id =
+                |id: &String| !id.is_empty() && id.bytes().all(|b| b.is_ascii_digit());
+            if !record.in_reply_to.as_ref().is_none_or(valid_reply_id) {
+                env::panic_str(&format!(
+                    "Record {}: in_reply_to must be a numeric id",
+                    index
+                ));
+            }
+        }
+
+        // Every record counts as one write; imports run by the owner for others aren't limited
+        if author == caller {
+            self.consume_rate_limit(&caller, records.len() as u32);
+        }
+
+        let mut tweet_ids = Vec::new();
+        for record in records {
+            let tweet = self.insert_tweet(
+                author.clone(),
+                Vec::new(),
+                None,
+                record.text,
+                None,
+                None,
+                Vec::new(),
+                Some(record.created_at),
+            );
+            self.record_op(
+                OperationKind::PostTweet,
+                Some(OpTarget::Tweet(tweet.id)),
+                None,
+            );
+            tweet_ids.push(tweet.id);
+        }
+
+        env::log_str(&format!(
+            "Imported {} archived tweets for @{}",
+            tweet_ids.len(),
+            author
+        ));
+        tweet_ids
+    }
+
+    // Register a short link - like POST /links on a URL shortener
+    // Returns the code to embed in tweets; the caller becomes the link's owner
+    pub fn register_short_link(&mut self, url: String) -> String {
+        self.count_call("register_short_link");
+
+        self.assert_content_creation_allowed();
+
+        if !(url.starts_with("https://") || url.starts_with("http://")) {
+            env::panic_str("Short link url must start with https:// or http://");
+        }
+        if url.len() > MAX_LINK_URL_LEN {
+            env::panic_str("Short link url is too long");
+        }
+
+        let owner = env::predecessor_account_id();
+        self.consume_rate_limit(&owner, 1);
+        let code = short_link_code(self.next_link_id);
+        self.next_link_id += 1;
+        self.short_links.insert(
+            code.clone(),
+            ShortLink {
+                code: code.clone(),
+                url: url.clone(),
+                owner: owner.clone(),
+                created_at: env::block_timestamp(),
+                clicks: 0,
+            },
+        );
+        self.short_links_by_owner
+            .entry(owner.clone())
+            .or_default()
+            .push(code.clone());
+
+        env::log_str(&format!(
+            "Short link {} -> {} registered by @{}",
+            code, url, owner
+        ));
+        self.record_op(
+            OperationKind::RegisterShortLink,
+            Some(OpTarget::ShortLink(code.clone())),
+            None,
+        );
+        code
+    }
+
+    // Count a click on a short link - optional, for clients that want click statistics
+    // Like the redirect endpoint of a URL shortener incrementing its counter
+    // Returns the new click count
+    pub fn record_click(&mut self, code: String) -> u64 {
+        self.count_call("record_click");
+
+        self.assert_not_decommissioning();
+        self.consume_rate_limit(&env::predecessor_account_id(), 1);
+
+        let link = self
+            .short_links
+            .get_mut(&code)
+            .unwrap_or_else(|| env::panic_str("Short link not found"));
+        link.clicks += 1;
+        let clicks = link.clicks;
+        self.record_op(
+            OperationKind::RecordClick,
+            Some(OpTarget::ShortLink(code)),
+            None,
+        );
+        clicks
+    }
+
+    // Post an event (meetup) announcement - a tweet with structured details attached
+    // Like POST /events: the tweet text is a readable summary, the details are stored as fields
+    pub fn post_event(
+        &mut self,
+        title: String,
+        starts_at: Timestamp,
+        location: String,
+        details: String,
+    ) -> EventDetails {
+        self.count_call("post_event");
+
+        self.assert_content_creation_allowed();
+
+        if title.trim().is_empty() || title.len() > MAX_EVENT_TITLE_LEN {
+            env::panic_str(&format!(
+                "Event title must be between 1 and {} bytes",
+                MAX_EVENT_TITLE_LEN
+            ));
+        }
+        if location.len() > MAX_EVENT_LOCATION_LEN || details.len() > MAX_EVENT_DETAILS_LEN {
+            env::panic_str("Event location or details are too long");
+        }
+
+        // The announcement is always stored on-chain, so it has to fit like a regular tweet
+        let announcement = format!(
+            "📅 {} - {} @ {}\n{}",
+            title,
+            format_rfc3339(starts_at),
+            location,
+            details
+        );
+        if let Some(max_bytes) = self.config.max_onchain_bytes {
+            if announcement.len() > max_bytes as usize {
+                env::panic_str(
+                    "Event announcement exceeds max_onchain_bytes: shorten the title, location or details",
+                );
+            }
+        }
+
+        let author = env::predecessor_account_id();
+        self.consume_rate_limit(&author, 1);
+        let tweet = self.insert_tweet(
+            author,
+            Vec::new(),
+            None,
+            announcement,
+            None,
+            None,
+            Vec::new(),
+            None,
+        );
+        self.record_op(
+            OperationKind::PostTweet,
+            Some(OpTarget::Tweet(tweet.id)),
+            None,
+        );
+
+        let event = EventDetails {
+            tweet_id: tweet.id,
+            title,
+            starts_at,
+            location,
+            details,
+            rsvp_count: 0,
+        };
+        self.event_details.insert(tweet.id, event.clone());
+        event
+    }
+
+    // Answer an event invitation - like PUT /events/{id}/rsvp
+    // Answering again replaces the previous answer
+    pub fn rsvp(&mut self, tweet_id: TweetId, status: RsvpStatus) -> EventDetails {
+        self.count_call("rsvp");
+
+        self.assert_not_decommissioning();
+
+        let account_id = env::predecessor_account_id();
+        self.consume_rate_limit(&account_id, 1);
+        let event = self
+            .event_details
+            .get_mut(&tweet_id)
+            .unwrap_or_else(|| env::panic_str("Event not found"));
+
+        // First answer: append the account to the attendee list (like INSERT ... ON CONFLICT)
+        let key = (tweet_id, account_id.clone());
+        if !self.rsvps.contains_key(&key) {
+            self.event_attendees
+                .insert((tweet_id, event.rsvp_count), account_id.clone());
+            event.rsvp_count += 1;
+        }
+        let event = event.clone();
+        self.rsvps.insert(
+            key,
+            Rsvp {
+                account_id: account_id.clone(),
+                status,
+                updated_at: env::block_timestamp(),
+            },
+        );
+
+        env::log_str(&format!(
+            "@{} answered {:?} to event #{}",
+            account_id, status, tweet_id
+        ));
+        self.record_op(OperationKind::Rsvp, Some(OpTarget::Tweet(tweet_id)), None);
+        event
+    }
+
+    // Register a live audio/video room - like POST /spaces
+    // Also posts a tweet announcing the space in the host's timeline
+    // stream_ref: where clients join (URL or room id of the off-chain streaming service)
+    pub fn create_space(
+        &mut self,
+        title: String,
+        starts_at: Timestamp,
+        stream_ref: String,
+    ) -> Space {
+        self.count_call("create_space");
+
+        self.assert_content_creation_allowed();
+
+        if title.trim().is_empty() || title.len() > MAX_SPACE_TITLE_LEN {
+            env::panic_str(&format!(
+                "Space title must be between 1 and {} bytes",
+                MAX_SPACE_TITLE_LEN
+            ));
+        }
+        if stream_ref.is_empty() || stream_ref.len() > MAX_CONTENT_URI_LEN {
+            env::panic_str(&format!(
+                "stream_ref must be between 1 and {} bytes",
+                MAX_CONTENT_URI_LEN
+            ));
+        }
+
+        // The announcement is always stored on-chain, so it has to fit like a regular tweet
+        let text = format!(
+            "🎙️ Space: {} - starts {}, join at {}",
+            title,
+            format_rfc3339(starts_at),
+            stream_ref
+        );
+        if let Some(max_bytes) = self.config.max_onchain_bytes {
+            if text.len() > max_bytes as usize {
+                env::panic_str(
+                    "Space announcement exceeds max_onchain_bytes: shorten the title or stream_ref",
+                );
+            }
+        }
+
+        let host = env::predecessor_account_id();
+        self.consume_rate_limit(&host, 1);
+        let announcement = self.insert_tweet(
+            host.clone(),
+            Vec::new(),
+            None,
+            text,
+            None,
+            None,
+            Vec::new(),
+            None,
+        );
+        self.record_op(
+            OperationKind::PostTweet,
+            Some(OpTarget::Tweet(announcement.id)),
+            None,
+        );
+
+        let space = Space {
+            id: self.next_space_id,
+            host,
+            cohosts: Vec::new(),
+            title,
+            starts_at,
+            stream_ref,
+            announcement_id: announcement.id,
+            ended_at: None,
+        };
+        self.live_spaces.insert(space.id, space.clone());
+        self.next_space_id += 1;
+
+        env::log_str(&format!("Space #{} created by @{}", space.id, space.host));
+        self.record_op(
+            OperationKind::CreateSpace,
+            Some(OpTarget::Space(space.id)),
+            None,
+        );
+        space
+    }
+
+    // End a space - host only. The space moves to the archive and leaves get_live_spaces
+    pub fn end_space(&mut self, space_id: u64) -> Space {
+        self.count_call("end_space");
+
+        let mut space = self.assert_space_host(space_id).clone();
+        self.live_spaces.remove(&space_id);
+
+        space.ended_at = Some(env::block_timestamp());
+        self.ended_spaces.insert(space_id, space.clone());
+
+        env::log_str(&format!("Space #{} ended by @{}", space_id, space.host));
+        self.record_op(
+            OperationKind::EndSpace,
+            Some(OpTarget::Space(space_id)),
+            None,
+        );
+        space
+    }
+
+    // Invite a co-host to a live space - host only
+    pub fn add_space_cohost(&mut self, space_id: u64, cohost: AccountId) -> Space {
+        self.count_call("add_space_cohost");
+
+        self.assert_not_decommissioning();
+        let space = self.assert_space_host(space_id);
+        if cohost == space.host || space.cohosts.contains(&cohost) {
+            env::panic_str("Account already hosts this space");
+        }
+        if space.cohosts.len() >= MAX_SPACE_COHOSTS {
+            env::panic_str(&format!(
+                "A space can have at most {} co-hosts",
+                MAX_SPACE_COHOSTS
+            ));
+        }
+
+        space.cohosts.push(cohost);
+        let space = space.clone();
+        self.record_op(
+            OperationKind::AddSpaceCohost,
+            Some(OpTarget::Space(space_id)),
+            None,
+        );
+        space
+    }
+
+    // Remove a co-host from a live space - host only
+    pub fn remove_space_cohost(&mut self, space_id: u64, cohost: AccountId) -> Space {
+        self.count_call("remove_space_cohost");
+
+        self.assert_not_decommissioning();
+        let space = self.assert_space_host(space_id);
+        space.cohosts.retain(|account_id| account_id != &cohost);
+        let space = space.clone();
+        self.record_op(
+            OperationKind::RemoveSpaceCohost,
+            Some(OpTarget::Space(space_id)),
+            None,
+        );
+        space
+    }
+
+    // Link an account to an external identity - only configured attesters may call this
+    // The attester checks the proof off-chain (e.g. a gist or post containing the NEAR account
+    // id) and records its hash; a new attestation replaces the account's link on that platform
+    // Like a verification service calling back POST /users/{id}/verified-accounts
+    pub fn attest_identity(
+        &mut self,
+        account_id: AccountId,
+        platform: IdentityPlatform,
+        handle: String,
+        proof_hash: Base64VecU8,
+    ) -> LinkedIdentity {
+        self.count_call("attest_identity");
+
+        self.assert_not_decommissioning();
+
+        let attester = env::predecessor_account_id();
+        if !self.config.attesters.contains(&attester) {
+            env::panic_str("Only configured attesters can attest identities");
+        }
+        let handle = handle.trim_start_matches('@').to_string();
+        if handle.is_empty()
+            || handle.len() > MAX_IDENTITY_HANDLE_LEN
+            || !handle
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            env::panic_str("Invalid identity handle");
+        }
+        if proof_hash.0.len() != 32 {
+            env::panic_str("proof_hash must be a SHA-256 hash (32 bytes)");
+        }
+
+        let identity = LinkedIdentity {
+            platform,
+            handle,
+            proof_hash,
+            attester,
+            attested_at: env::block_timestamp(),
+        };
+        let identities = self.identities.entry(account_id.clone()).or_default();
+        identities.retain(|existing| existing.platform != platform);
+        identities.push(identity.clone());
+
+        env::log_str(&format!(
+            "@{} attested that @{} is {} on {}",
+            identity.attester,
+            account_id,
+            identity.handle,
+            platform.name()
+        ));
+        self.record_op(
+            OperationKind::AttestIdentity,
+            Some(OpTarget::Account(account_id)),
+            None,
+        );
+        identity
+    }
+
+    // Remove the caller's linked identity on a platform - accounts can always unlink themselves
+    // Like DELETE /users/me/verified-accounts/{platform}
+    // Returns the op id of the write (see get_op)
+    pub fn unlink_identity(&mut self, platform: IdentityPlatform) -> u64 {
+        self.count_call("unlink_identity");
+
+        let account_id = env::predecessor_account_id();
+        if let Some(identities) = self.identities.get_mut(&account_id) {
+            identities.retain(|identity| identity.platform != platform);
+        }
+        env::log_str(&format!(
+            "@{} unlinked their {} identity",
+            account_id,
+            platform.name()
+        ));
+        self.record_op(
+            OperationKind::UnlinkIdentity,
+            Some(OpTarget::Account(account_id)),
+            None,
+        )
+    }
+
+    // Store a post forwarded by a peer contract - like a webhook receiver endpoint
+    // Only accounts listed in federation_peers may call this (cross-contract calls are
+    // authenticated by the runtime: the predecessor is the calling contract)
+    pub fn receive_federated_post(&mut self, post: FederatedPost) {
+        self.count_call("receive_federated_post");
+
+        self.assert_content_creation_allowed();
+
+        let origin = env::predecessor_account_id();
+        if !self.config.federation_peers.contains(&origin) {
+            env::panic_str("Only federation peers can forward posts");
+        }
+
+        // Tag the post with the peer it actually came from
+        let post = FederatedPost { origin, ..post };
+        env::log_str(&format!(
+            "Federated post #{} by @{} received from {}",
+            post.tweet_id, post.author, post.origin
+        ));
+        self.federated_posts.push(post);
+    }
+
+    // Remove search index entries of deleted tweets - a maintenance job anyone can run
+    // (e.g. a keeper bot), like a background cleanup worker processing a job queue.
+    // Processes at most `limit` deleted tweets per call to stay within the gas limit;
+    // the queue itself is the persisted cursor, so calls can simply be repeated.
+    // Returns how many deleted tweets are still waiting to be cleaned up
+    pub fn gc_indexes(&mut self, limit: u32) -> u32 {
+        self.count_call("gc_indexes");
+
+        for _ in 0..limit {
+            let Some((tweet_id, terms)) = self.gc_queue.pop() else {
+                break;
+            };
+            for term in terms {
+                if let Some(tweet_ids) = self.search_index.get_mut(&term) {
+                    tweet_ids.retain(|id| *id != tweet_id);
+                    if tweet_ids.is_empty() {
+                        self.search_index.remove(&term);
+                    }
+                }
+            }
+        }
+
+        let remaining = self.gc_queue.len();
+        env::log_str(&format!(
+            "Index GC done, {} deleted tweets remaining",
+            remaining
+        ));
+        remaining
+    }
+
+    // Delete a tweet - equivalent to DELETE /tweets/{id} endpoint
+    // Only the tweet author can delete their own tweets (authorization check)
+    // Returns the op id of the deletion (see get_op), or None if nothing was deleted
+    pub fn delete_tweet(&mut self, tweet_id: TweetId) -> Option<u64> {
+        self.count_call("delete_tweet");
+
+        // Get who's trying to delete the tweet (like checking JWT/session)
+        let caller = env::predecessor_account_id();
+
+        // Check if tweet exists and verify ownership
+        // This is like: SELECT author FROM tweets WHERE id = ?
+        if let Some(tweet) = self.tweets.get(&tweet_id) {
+            // Authorization check - only author can delete their tweet
+            // Similar to checking if user owns the resource in REST API
+            if tweet.author == caller {
+                // A legal hold overrides the author's right to delete
+                if tweet.frozen {
+                    env::panic_str("Tweet is frozen by a legal hold");
+                }
+
+                // Delete the tweet from storage
+                // Like: DELETE FROM tweets WHERE id = ?
+                self.remove_tweet(tweet_id);
+                env::log_str(&format!("Tweet #{} deleted by @{}", tweet_id, caller));
+                self.emit_event(TwitterEvent::TweetDeleted, tweet_id, &caller);
+                Some(self.record_op(
+                    OperationKind::DeleteTweet,
+                    Some(OpTarget::Tweet(tweet_id)),
+                    None,
+                ))
+            } else {
+                // Unauthorized deletion attempt - log security event
+                // In REST API, this would be 403 Forbidden
+                env::log_str(&format!(
+                    "User @{} attempted to delete tweet #{} but is not the author.",
+                    caller, tweet_id
+                ));
+                None
+            }
+        } else {
+            // Tweet doesn't exist - log the attempt
+            // In REST API, this would be 404 Not Found
+            env::log_str(&format!(
+                "Attempt to delete non-existent tweet #{} by @{}",
+                tweet_id, caller
+            ));
+            None
+        }
+    }
+
+    // Label your own tweet as age-restricted - like PATCH /tweets/{id} {"nsfw": true}
+    // Authors can only add the label: removing it is up to moderators (see moderate_adult_flag),
+    // so a moderator's label sticks
+    pub fn set_adult_flag(&mut self, tweet_id: TweetId, adult: bool) -> Tweet {
+        self.count_call("set_adult_flag");
+
+        self.assert_not_decommissioning();
+
+        let caller = env::predecessor_account_id();
+        let tweet = self
+            .tweets
+            .get(&tweet_id)
+            .unwrap_or_else(|| env::panic_str("Tweet not found"));
+        if tweet.author != caller {
+            env::panic_str("Only the author can label a tweet: moderators use moderate_adult_flag");
+        }
+        if !adult {
+            env::panic_str("Only moderators can remove an adult label");
+        }
+        self.consume_rate_limit(&caller, 1);
+        self.apply_adult_flag(tweet_id, adult)
+    }
+
+    // Maintenance sweep for auto-expiring tweets (see AccountSettings::expire_after_days)
+    // Anyone can call it, e.g. a keeper bot. Each call looks at up to `limit` tweet ids
+    // after where the previous call stopped, wrapping around at the end, and deletes the
+    // tweets whose author's expiry has passed. Returns how many tweets were deleted
+    // Like a cron job running DELETE FROM tweets WHERE ... LIMIT n in small batches
+    pub fn expire_tweets(&mut self, limit: u32) -> u32 {
+        self.count_call("expire_tweets");
+
+        let now = env::block_timestamp();
+        let mut deleted = 0;
+        for _ in 0..u64::from(limit).min(MAX_PAGE_SCAN).min(self.next_tweet_id) {
+            if self.expiry_cursor >= self.next_tweet_id {
+                self.expiry_cursor = 0;
+            }
+            let tweet_id = TweetId(self.expiry_cursor);
+            self.expiry_cursor += 1;
+
+            let Some(tweet) = self.tweets.get(&tweet_id).filter(|tweet| !tweet.frozen) else {
+                continue;
+            };
+            let Some(days) = self
+                .settings
+                .get(&tweet.author)
+                .and_then(|settings| settings.expire_after_days)
+            else {
+                continue;
+            };
+            // Imported tweets age from when they were originally written
+            let written_at = tweet.original_created_at.unwrap_or(tweet.timestamp);
+            if now.saturating_sub(written_at) < u64::from(days) * NANOS_PER_DAY {
+                continue;
+            }
+
+            let author = tweet.author.clone();
+            self.remove_tweet(tweet_id);
+            env::log_str(&format!("Tweet #{} of @{} expired", tweet_id, author));
+            self.emit_event(TwitterEvent::TweetDeleted, tweet_id, &author);
+            self.record_op(
+                OperationKind::ExpireTweet,
+                Some(OpTarget::Tweet(tweet_id)),
+                None,
+            );
+            deleted += 1;
+        }
+        deleted
+    }
+
+    // ============================================================================================
+    // OWNER METHODS (Administrative operations)
+    // ============================================================================================
+    // The owner is the account the contract is deployed to - whoever holds its full-access keys
+    // Think of these as admin-only endpoints protected by an "admin" role check
+
+    // Switch the contract between normal and read-only mode - like PUT /admin/mode
+    // Read-only mode keeps views and deletes working but blocks new tweets,
+    // which is handy while migrating state or investigating an incident
+    pub fn set_mode(&mut self, mode: ContractMode) {
+        self.count_call("set_mode");
+
+        self.assert_owner();
+        if self.legacy_migration.is_some() && mode == ContractMode::Active {
+            env::panic_str("Legacy tweets are still being migrated");
+        }
+
+        self.mode = mode;
+        env::log_str(&format!("Contract mode set to {:?}", mode));
+    }
+
+    // Replace the contract settings - like PUT /admin/config
+    pub fn update_config(&mut self, config: Config) {
+        self.count_call("update_config");
+
+        self.assert_owner();
+        if config
+            .rate_limit
+            .as_ref()
+            .is_some_and(|limit| limit.capacity == 0 || limit.refill_interval == 0)
+        {
+            env::panic_str("Rate limit capacity and refill_interval must be positive");
+        }
+        if config.ranking.recency_half_life == 0 {
+            env::panic_str("Ranking recency_half_life must be positive");
+        }
+        if config.federation_peers.len() > MAX_FEDERATION_PEERS {
+            env::panic_str(&format!(
+                "Too many federation peers: at most {} are allowed",
+                MAX_FEDERATION_PEERS
+            ));
+        }
+
+        env::log_str(&format!("Config updated: {:?}", config));
+        self.config = config;
+    }
+
+    // Record a digest of key state at the current block - like a scheduled checksum job
+    // Works in read-only mode too, so snapshots can bracket a maintenance window
+    pub fn create_snapshot(&mut self) -> Snapshot {
+        self.count_call("create_snapshot");
+
+        self.assert_owner();
+
+        let (recent_tweets, recent_tweets_hash) = self.recent_tweets_hash();
+        let snapshot = Snapshot {
+            id: self.snapshots.len(),
+            block_height: env::block_height(),
+            timestamp: env::block_timestamp(),
+            tweet_count: self.tweets.len(),
+            next_tweet_id: self.next_tweet_id,
+            next_op_id: self.next_op_id,
+            recent_tweets,
+            recent_tweets_hash: recent_tweets_hash.to_vec().into(),
+            tweets_root: merkle_root(&self.tree_peaks(self.next_tweet_id)),
+        };
+        self.snapshots.push(snapshot.clone());
+
+        env::log_str(&format!(
+            "Snapshot #{} taken at block {}",
+            snapshot.id, snapshot.block_height
+        ));
+        snapshot
+    }
+
+    // Put a tweet under a legal hold (e.g. a pending dispute) - like a litigation hold flag
+    // A frozen tweet can't be deleted by its author or expire; views show `frozen: true`
+    // and the hold is recorded in the operation log. Works in read-only mode too
+    pub fn freeze_tweet(&mut self, tweet_id: TweetId) -> Tweet {
+        self.count_call("freeze_tweet");
+
+        self.assert_owner();
+        self.set_frozen(tweet_id, true)
+    }
+
+    // Release a legal hold - the author can delete the tweet again
+    pub fn unfreeze_tweet(&mut self, tweet_id: TweetId) -> Tweet {
+        self.count_call("unfreeze_tweet");
+
+        self.assert_owner();
+        self.set_frozen(tweet_id, false)
+    }
+
+    // Label any tweet as age-restricted or remove the label, as moderator - like a
+    // moderation queue action on PATCH /admin/tweets/{id}
+    pub fn moderate_adult_flag(&mut self, tweet_id: TweetId, adult: bool) -> Tweet {
+        self.count_call("moderate_adult_flag");
+
+        self.assert_owner();
+        if self.grace_period_over() {
+            env::panic_str("Decommission grace period is over: adult labels can't change");
+        }
+        if !self.tweets.contains_key(&tweet_id) {
+            env::panic_str("Tweet not found");
+        }
+        self.apply_adult_flag(tweet_id, adult)
+    }
+
+    // Add a topic to the curated registry - like POST /admin/topics
+    pub fn create_topic(&mut self, name: String, description: String) -> Topic {
+        self.count_call("create_topic");
+
+        self.assert_owner();
+
+        // Validate input (like request validation middleware)
+        if name.trim().is_empty() || name.len() > MAX_TOPIC_NAME_LEN {
+            env::panic_str(&format!(
+                "Topic name must be between 1 and {} bytes",
+                MAX_TOPIC_NAME_LEN
+            ));
+        }
+        if description.len() > MAX_TOPIC_DESCRIPTION_LEN {
+            env::panic_str("Topic description is too long");
+        }
+        // Names must be unique (like a UNIQUE constraint) - the registry is small, so scan it
+        if self.topics.iter().any(|topic| topic.name == name) {
+            env::panic_str("A topic with this name already exists");
+        }
+
+        let topic = Topic {
+            id: self.topics.len(),
+            name,
+            description,
+        };
+        self.topics.push(topic.clone());
+
+        env::log_str(&format!("Topic #{} '{}' created", topic.id, topic.name));
+        topic
+    }
+
+    // Register a contract to be notified about events - like POST /admin/webhooks
+    // event_mask is a sum of TwitterEvent bits (1 = posted, 2 = liked, 4 = deleted).
+    // Notifications are best-effort calls to the listener's on_twitter_event(event, tweet_id,
+    // account_id) method with a fixed LISTENER_GAS; a failing listener never blocks the
+    // action.
+    // Registering an existing listener again replaces its event mask.
+    pub fn register_listener(&mut self, contract_id: AccountId, event_mask: u32) {
+        self.count_call("register_listener");
+
+        self.assert_owner();
+
+        if event_mask == 0 || event_mask & !ALL_EVENTS_MASK != 0 {
+            env::panic_str("Invalid event mask");
+        }
+        if !self.listeners.contains_key(&contract_id) && self.listeners.len() >= MAX_LISTENERS {
+            env::panic_str(&format!(
+                "Too many listeners: at most {} can be registered",
+                MAX_LISTENERS
+            ));
+        }
+
+        self.listeners.insert(contract_id.clone(), event_mask);
+        env::log_str(&format!(
+            "Listener {} registered with event mask {}",
+            contract_id, event_mask
+        ));
+    }
+
+    // Stop notifying a listener contract - like DELETE /admin/webhooks/{id}
+    pub fn unregister_listener(&mut self, contract_id: AccountId) {
+        self.count_call("unregister_listener");
+
+        self.assert_owner();
+
+        if self.listeners.remove(&contract_id).is_none() {
+            env::panic_str("Listener is not registered");
+        }
+        env::log_str(&format!("Listener {} unregistered", contract_id));
+    }
+
+    // Start a responsible shutdown of the contract - step 1 of 4
+    // Like announcing the sunset of a service: writes are frozen immediately, but users get
+    // a grace period to export their data (all views keep working) and delete their tweets
+    pub fn decommission(&mut self) {
+        self.count_call("decommission");
+
+        self.assert_owner();
+
+        if self.decommission_after.is_some() {
+            env::panic_str("Decommission has already been started");
+        }
+
+        // Timelock: the account can only be deleted after the grace period
+        let decommission_after = env::block_timestamp() + DECOMMISSION_GRACE_PERIOD;
+        self.decommission_after = Some(decommission_after);
+
+        env::log_str(&format!(
+            "Contract decommission started. Writes are frozen, account can be deleted after {}",
+            decommission_after
+        ));
+    }
+
+    // Call off an announced shutdown - like postponing a sunset after user feedback
+    // Possible until the first storage key has been removed
+    pub fn cancel_decommission(&mut self) {
+        self.count_call("cancel_decommission");
+
+        self.assert_owner();
+        if self.decommission_after.is_none() {
+            env::panic_str("Decommission has not been started");
+        }
+        if self
+            .decommission_progress
+            .as_ref()
+            .is_some_and(|progress| progress.keys_removed > 0 || progress.deletion_pending)
+        {
+            env::panic_str("State removal has started: the decommission can't be cancelled");
+        }
+
+        self.decommission_after = None;
+        self.decommission_progress = None;
+        env::log_str("Contract decommission cancelled. Writes are enabled again");
+    }
+
+    // Finish the shutdown - step 2 of 4, only possible once the grace period is over
+    // Hashes up to `limit` tweet ids into the final state digest (so archives can prove their
+    // export is complete); call it again until it returns the digest, which is also logged
+    // Tweets can't be deleted any more once the grace period is over, so the digest is final
+    pub fn digest_final_state(&mut self, limit: u32) -> Option<Base64VecU8> {
+        self.count_call("digest_final_state");
+
+        self.assert_owner();
+        self.assert_grace_period_over();
+
+        let next_tweet_id = self.next_tweet_id;
+        let progress = self
+            .decommission_progress
+            .get_or_insert_with(|| DecommissionProgress {
+                next_digest_id: 0,
+                digest: vec![0u8; 32].into(),
+                keys_removed: 0,
+                deletion_pending: false,
+            });
+        if progress.next_digest_id == next_tweet_id {
+            return Some(progress.digest.clone());
+        }
+
+        // Same chain as before, just resumable: h = sha256(h || borsh(tweet)) in id order
+        let scan_end =
+            next_tweet_id.min(progress.next_digest_id + limit.min(MAX_PAGE_SCAN as u32) as u64);
+        let mut digest = progress.digest.0.clone();
+        for tweet_id in progress.next_digest_id..scan_end {
+            if let Some(tweet) = self.tweets.get(&TweetId(tweet_id)) {
+                digest.extend(near_sdk::borsh::to_vec(tweet).expect("Tweet is serializable"));
+                digest = env::sha256(&digest);
+            }
+        }
+        progress.next_digest_id = scan_end;
+        progress.digest = digest.into();
+        if scan_end < next_tweet_id {
+            return None;
+        }
+
+        // Final state snapshot - like publishing a checksum next to a database dump
+        let hex: String = progress
+            .digest
+            .0
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+        env::log_str(&format!(
+            "Final state snapshot: {} tweets, next tweet id {}, sha256 digest {}",
+            self.tweets.len(),
+            next_tweet_id,
+            hex
+        ));
+        Some(progress.digest.clone())
+    }
+
+    // Step 3 of 4: remove raw storage keys, in batches, so the account becomes small enough
+    // to be deleted (NEAR refuses to delete accounts with large state)
+    // Like dropping tables one chunk at a time: list the keys with an RPC view_state query
+    // (e.g. `near contract view-storage <account> all as-json`) and pass them here
+    // The contract's own state record ("STATE") is removed together with the account
+    pub fn remove_state_keys(&mut self, keys: Vec<Base64VecU8>) -> u32 {
+        self.count_call("remove_state_keys");
+
+        self.assert_owner();
+        let progress = self.digested_progress();
+        if progress.deletion_pending {
+            env::panic_str("Account deletion is already in progress");
+        }
+
+        let mut removed = 0;
+        for key in keys {
+            if key.0 == b"STATE" {
+                env::panic_str("The contract state record is removed together with the account");
+            }
+            if env::storage_remove(&key.0) {
+                removed += 1;
+            }
+        }
+        self.digested_progress().keys_removed += removed as u64;
+        env::log_str(&format!("Removed {} storage keys", removed));
+        removed
+    }
+
+    // Step 4 of 4: delete the contract account, sending its remaining balance to the
+    // beneficiary - like shutting down the server after the final backup
+    // If NEAR rejects the deletion (too much state left), on_decommission_finalized logs it
+    // and this can be called again after removing more keys
+    pub fn finalize_decommission(&mut self, beneficiary_id: AccountId) -> Promise {
+        self.count_call("finalize_decommission");
+
+        self.assert_owner();
+        let progress = self.digested_progress();
+        if progress.deletion_pending {
+            env::panic_str("Account deletion is already in progress");
+        }
+        progress.deletion_pending = true;
+
+        env::log_str(&format!(
+            "Deleting the contract account, beneficiary @{}",
+            beneficiary_id
+        ));
+        Promise::new(env::current_account_id())
+            .delete_account(beneficiary_id)
+            .then(Promise::new(env::current_account_id()).function_call(
+                "on_decommission_finalized".to_string(),
+                b"{}".to_vec(),
+                NearToken::from_yoctonear(0),
+                DECOMMISSION_CALLBACK_GAS,
+            ))
+    }
+
+    // Callback of finalize_decommission - it only gets to run when the deletion failed,
+    // because a successful deletion removes the contract together with its account
+    #[private]
+    pub fn on_decommission_finalized(
+        &mut self,
+        #[callback_result] result: Result<(), PromiseError>,
+    ) -> bool {
+        self.count_call("on_decommission_finalized");
+
+        let deleted = result.is_ok();
+        if let Some(progress) = self.decommission_progress.as_mut() {
+            progress.deletion_pending = false;
+        }
+        if deleted {
+            env::log_str("Contract account deleted");
+        } else {
+            env::log_str(
+                "Account deletion failed: remove more storage keys with remove_state_keys, \
+                 then call finalize_decommission again",
+            );
+        }
+        deleted
+    }
+
+    // Callback of an approved admin proposal's call: records whether the call succeeded
+    // The method may return anything (or nothing), so the raw promise result is checked
+    // instead of deserializing it with #[callback_result]
+    #[private]
+    pub fn on_admin_proposal_executed(&mut self, proposal_id: u64) -> bool {
+        self.count_call("on_admin_proposal_executed");
+
+        let succeeded = matches!(env::promise_result(0), PromiseResult::Successful(_));
+        let proposal = self
+            .admin_proposals
+            .get_mut(&proposal_id)
+            .unwrap_or_else(|| env::panic_str("Admin proposal not found"));
+        proposal.status = if succeeded {
+            AdminProposalStatus::Executed
+        } else {
+            AdminProposalStatus::Failed
+        };
+        env::log_str(&format!(
+            "Admin proposal #{} {}: {}",
+            proposal_id,
+            if succeeded { "executed" } else { "failed" },
+            proposal.method
+        ));
+        succeeded
+    }
+
+    // ============================================================================================
+    // ADMIN COUNCIL (M-of-N approvals for owner methods)
+    // ============================================================================================
+    // Like a "two-person rule" for production changes: once a council is set, owner methods
+    // only run after enough members approve them, so no single key can change the contract.
+    // An approved proposal runs as a call the contract makes to itself, without an external
+    // multisig contract
+
+    // Set, replace or dissolve the admin council - like PUT /admin/council
+    // This is an owner method too, so once a council exists only an approved proposal can
+    // change it. Replacing the council voids the old council's pending proposals.
+    // Pass no members and threshold 0 to hand owner methods back to the contract account's key
+    pub fn set_admin_council(&mut self, members: Vec<AccountId>, threshold: u32) {
+        self.count_call("set_admin_council");
+
+        self.assert_owner();
+        if members.is_empty() && threshold == 0 {
+            self.admin_council = None;
+            env::log_str("Admin council dissolved: owner methods need the contract account again");
+            return;
+        }
+
+        let mut members = members;
+        members.sort_unstable();
+        members.dedup();
+        if members.len() > MAX_ADMIN_COUNCIL_MEMBERS {
+            env::panic_str(&format!(
+                "Too many admin council members: at most {} are allowed",
+                MAX_ADMIN_COUNCIL_MEMBERS
+            ));
+        }
+        if members.contains(&env::current_account_id()) {
+            env::panic_str("The contract account can't be an admin council member");
+        }
+        if threshold == 0 || threshold as usize > members.len() {
+            env::panic_str("Council threshold must be between 1 and the number of members");
+        }
+
+        env::log_str(&format!(
+            "Admin council set: {} of {} members must approve owner methods",
+            threshold,
+            members.len()
+        ));
+        self.admin_council = Some(AdminCouncil {
+            members,
+            threshold,
+            first_proposal_id: self.next_admin_proposal_id,
+        });
+    }
+
+    // Propose an owner method call - like opening a change request that needs approvals
+    // args are the method's arguments as a JSON object, e.g. "{\"mode\": \"ReadOnly\"}"
+    // The proposer's approval counts, so with a threshold of 1 the call runs right away
+    pub fn propose_admin_action(&mut self, method: String, args: String) -> AdminProposal {
+        self.count_call("propose_admin_action");
+
+        let proposer = self.assert_council_member();
+        let is_owner_method = API_METHODS
+            .iter()
+            .any(|spec| spec.kind == "owner" && spec.name == method);
+        if !is_owner_method || NON_PROPOSABLE_METHODS.contains(&method.as_str()) {
+            env::panic_str(&format!("Not an owner method: {}", method));
+        }
+        let is_object = near_sdk::serde_json::from_str::<near_sdk::serde_json::Value>(&args)
+            .is_ok_and(|value| value.is_object());
+        if !is_object {
+            env::panic_str("Admin action args must be a JSON object");
+        }
+
+        let now = env::block_timestamp();
+        let proposal = AdminProposal {
+            id: self.next_admin_proposal_id,
+            proposer: proposer.clone(),
+            method,
+            args,
+            approvals: vec![proposer.clone()],
+            created_at: now,
+            expires_at: now + ADMIN_PROPOSAL_TTL,
+            status: AdminProposalStatus::Pending,
+        };
+        self.next_admin_proposal_id += 1;
+
+        env::log_str(&format!(
+            "Admin proposal #{} by @{}: {}",
+            proposal.id, proposer, proposal.method
+        ));
+        self.record_op(
+            OperationKind::ProposeAdminAction,
+            Some(OpTarget::AdminProposal(proposal.id)),
+            None,
+        );
+        self.settle_admin_proposal(proposal)
+    }
+
+    // Approve a pending admin proposal - like approving a change request
+    // The approval that reaches the council's threshold dispatches the call
+    pub fn approve(&mut self, proposal_id: u64) -> AdminProposal {
+        self.count_call("approve");
+
+        let member = self.assert_council_member();
+        let first_proposal_id = self
+            .admin_council
+            .as_ref()
+            .map_or(0, |council| council.first_proposal_id);
+        let mut proposal = self
+            .admin_proposals
+            .get(&proposal_id)
+            .cloned()
+            .unwrap_or_else(|| env::panic_str("Admin proposal not found"));
+
+        if proposal.status != AdminProposalStatus::Pending {
+            env::panic_str("Admin proposal was already executed");
+        }
+        if proposal_id < first_proposal_id {
+            env::panic_str("Admin proposal was made for a previous council");
+        }
+        if env::block_timestamp() >= proposal.expires_at {
+            env::panic_str("Admin proposal has expired");
+        }
+        if proposal.approvals.contains(&member) {
+            env::panic_str("Admin proposal already approved by this member");
+        }
+        proposal.approvals.push(member.clone());
+
+        env::log_str(&format!(
+            "Admin proposal #{} approved by @{} ({} approvals)",
+            proposal_id,
+            member,
+            proposal.approvals.len()
+        ));
+        self.record_op(
+            OperationKind::ApproveAdminAction,
+            Some(OpTarget::AdminProposal(proposal_id)),
+            None,
+        );
+        self.settle_admin_proposal(proposal)
+    }
+
+    // ============================================================================================
+    // READ METHODS (Free, don't modify state)
+    // ============================================================================================
+    // These are "view" methods - they don't cost gas and don't modify contract state
+    // Think of these as GET endpoints in your REST API
+
+    // Get all tweets with pagination - like GET /tweets?offset=0&limit=10
+    // from_index: starting position (like OFFSET in SQL)
+    // limit: maximum number of tweets to return (like LIMIT in SQL)
+    // include_adult: also return age-restricted tweets (explicit opt-in, default false);
+    // the same parameter works on every list view below
+    pub fn get_all_tweets(
+        &self,
+        from_index: Option<u64>,
+        limit: Option<u64>,
+        include_adult: Option<bool>,
+    ) -> Vec<Tweet> {
+        // Set default values if not provided (common REST API pattern)
+        let start = from_index.unwrap_or(0);
+        let limit_val = limit.unwrap_or(10);
+
+        // Query tweets with pagination (like SELECT * FROM tweets LIMIT x OFFSET y)
+        self.tweets
+            .iter() // Iterate over all tweets
+            .filter(|(_key, tweet)| is_listed(tweet, include_adult)) // WHERE NOT adult
+            .skip(start as usize) // Skip 'start' number of tweets (OFFSET)
+            .take(limit_val as usize) // Take only 'limit_val' tweets (LIMIT)
+            .map(|(_key, tweet)| tweet.clone()) // Extract tweet objects (ignore keys)
+            .collect() // Collect into Vector to return
+    }
+
+    // Get a page of tweets with only the requested fields - like JSON:API sparse fieldsets
+    // (GET /tweets?fields=id,text,likes). Smaller responses, and fields that aren't requested
+    // are never serialized, which saves gas on large pages. None returns every field
+    pub fn get_all_tweets_sparse(
+        &self,
+        from_index: Option<u64>,
+        limit: Option<u64>,
+        fields: Option<Vec<String>>,
+        include_adult: Option<bool>,
+    ) -> Vec<near_sdk::serde_json::Value> {
+        let fields = match fields {
+            Some(fields) => {
+                if let Some(unknown) = fields
+                    .iter()
+                    .find(|field| !TWEET_FIELDS.contains(&field.as_str()))
+                {
+                    env::panic_str(&format!("Unknown tweet field: {}", unknown));
+                }
+                fields
+            }
+            None => TWEET_FIELDS.iter().map(|field| field.to_string()).collect(),
+        };
+
+        self.tweets
+            .values()
+            .filter(|tweet| is_listed(tweet, include_adult))
+            .skip(from_index.unwrap_or(0) as usize)
+            .take(limit.unwrap_or(10) as usize)
+            .map(|tweet| select_tweet_fields(tweet, &fields))
+            .collect()
+    }
+
+    // Get tweets posted strictly before a timestamp, newest first - like
+    // GET /tweets?before=<ts>&limit=10 for infinite scroll: pass the timestamp of the oldest
+    // tweet you have to load the next page
+    pub fn get_tweets_before(
+        &self,
+        timestamp: Timestamp,
+        limit: Option<u64>,
+        include_adult: Option<bool>,
+    ) -> Vec<Tweet> {
+        let end = self.first_tweet_id_at_or_after(timestamp, false);
+        (0..end)
+            .rev()
+            .filter_map(|id| self.tweets.get(&TweetId(id)).cloned()) // Skip deleted tweets
+            .filter(|tweet| is_listed(tweet, include_adult))
+            .take(limit.unwrap_or(10) as usize)
+            .collect()
+    }
+
+    // Get tweets posted strictly after a timestamp, oldest first - like
+    // GET /tweets?after=<ts>&limit=10 for polling: pass the timestamp of the newest tweet you have
+    pub fn get_tweets_after(
+        &self,
+        timestamp: Timestamp,
+        limit: Option<u64>,
+        include_adult: Option<bool>,
+    ) -> Vec<Tweet> {
+        let start = self.first_tweet_id_at_or_after(timestamp, true);
+        (start..self.next_tweet_id)
+            .filter_map(|id| self.tweets.get(&TweetId(id)).cloned()) // Skip deleted tweets
+            .filter(|tweet| is_listed(tweet, include_adult))
+            .take(limit.unwrap_or(10) as usize)
+            .collect()
+    }
+
+    // Get specific tweet by ID - like GET /tweets/{id}
+    pub fn get_tweet_by_id(&self, tweet_id: TweetId) -> Option<Tweet> {
+        // Simple lookup by primary key
+        // Like: SELECT * FROM tweets WHERE id = ?
+        self.tweets.get(&tweet_id).cloned()
+    }
+
+    // Get registered listener contracts and their event masks - like GET /admin/webhooks
+    pub fn get_listeners(&self) -> Vec<(AccountId, u32)> {
+        self.listeners
+            .iter()
+            .map(|(contract_id, event_mask)| (contract_id.clone(), *event_mask))
+            .collect()
+    }
+
+    // Get posts received from peer communities with pagination - like GET /federated?offset=0&limit=10
+    pub fn get_federated_posts(
+        &self,
+        from_index: Option<u32>,
+        limit: Option<u32>,
+    ) -> Vec<FederatedPost> {
+        let start = from_index.unwrap_or(0);
+        let limit_val = limit.unwrap_or(10);
+
+        self.federated_posts
+            .iter()
+            .skip(start as usize)
+            .take(limit_val as usize)
+            .cloned()
+            .collect()
+    }
+
+    // Look up a write in the operation log - like GET /operations/{id}
+    // None if the op id was never assigned or has been pruned (only recent ops are kept)
+    pub fn get_op(&self, op_id: u64) -> Option<Operation> {
+        self.operations.get(&op_id).cloned()
+    }
+
+    // Get the admin council - None while the contract account's key calls owner methods alone
+    pub fn get_admin_council(&self) -> Option<AdminCouncil> {
+        self.admin_council.clone()
+    }
+
+    // Get an admin proposal with its approvals - like GET /admin/proposals/{id}
+    pub fn get_admin_proposal(&self, proposal_id: u64) -> Option<AdminProposal> {
+        self.admin_proposals.get(&proposal_id).cloned()
+    }
+
+    // Get a pending co-authored draft by ID - like GET /drafts/{id}
+    // Returns None once the draft has been published, rejected, cancelled or discarded
+    pub fn get_draft(&self, draft_id: u64) -> Option<Draft> {
+        self.drafts.get(&draft_id).cloned()
+    }
+
+    // Get the posting delegations an account has granted, including revoked ones
+    // Like GET /users/{id}/delegates?include=revoked - the full on-chain audit trail
+    pub fn get_posting_delegates(&self, account_id: AccountId) -> Vec<Delegation> {
+        self.delegations
+            .get(&account_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    // Get a persona with its owner and manager history - like GET /orgs/{handle}
+    pub fn get_persona(&self, handle: String) -> Option<Persona> {
+        self.personas.get(&handle).cloned()
+    }
+
+    // Get an account's settings - like GET /users/{id}/settings
+    // Accounts that never changed anything get the defaults
+    pub fn get_settings(&self, account_id: AccountId) -> AccountSettings {
+        self.settings.get(&account_id).cloned().unwrap_or_default()
+    }
+
+    // Get an account's attested external identities - like the verified links on a profile
+    pub fn get_linked_identities(&self, account_id: AccountId) -> Vec<LinkedIdentity> {
+        self.identities
+            .get(&account_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    // Get an event tweet's details - like GET /events/{id}
+    pub fn get_event(&self, tweet_id: TweetId) -> Option<EventDetails> {
+        self.event_details.get(&tweet_id).cloned()
+    }
+
+    // Get an event's attendee list, optionally only one kind of answer
+    // Like GET /events/{id}/rsvps?status=Going
+    pub fn get_event_attendees(
+        &self,
+        tweet_id: TweetId,
+        status: Option<RsvpStatus>,
+        from_index: Option<u32>,
+        limit: Option<u32>,
+    ) -> Vec<Rsvp> {
+        let Some(event) = self.event_details.get(&tweet_id) else {
+            return Vec::new();
+        };
+        (0..event.rsvp_count)
+            .filter_map(|index| self.event_attendees.get(&(tweet_id, index)))
+            .filter_map(|account_id| self.rsvps.get(&(tweet_id, account_id.clone())))
+            .filter(|rsvp| status.is_none_or(|status| rsvp.status == status))
+            .skip(from_index.unwrap_or(0) as usize)
+            .take(limit.unwrap_or(10) as usize)
+            .cloned()
+            .collect()
+    }
+
+    // Get spaces that haven't ended (live now or scheduled) - like GET /spaces?state=live
+    pub fn get_live_spaces(&self, from_index: Option<u32>, limit: Option<u32>) -> Vec<Space> {
+        self.live_spaces
+            .values()
+            .skip(from_index.unwrap_or(0) as usize)
+            .take(limit.unwrap_or(10) as usize)
+            .cloned()
+            .collect()
+    }
+
+    // Get a space by id, whether it is live or has ended - like GET /spaces/{id}
+    pub fn get_space(&self, space_id: u64) -> Option<Space> {
+        self.live_spaces
+            .get(&space_id)
+            .or_else(|| self.ended_spaces.get(&space_id))
+            .cloned()
+    }
+
+    // Resolve a short link code - like GET /{code} on a URL shortener, without the redirect
+    pub fn resolve_link(&self, code: String) -> Option<ShortLink> {
+        self.short_links.get(&code).cloned()
+    }
+
+    // Check that a copy of a tweet's text matches the on-chain record - like comparing checksums
+    // Returns false if the text differs or the tweet doesn't exist (anymore)
+    pub fn verify_tweet_content(&self, tweet_id: TweetId, text: String) -> bool {
+        self.tweets
+            .get(&tweet_id)
+            .is_some_and(|tweet| tweet.content_hash == content_hash(&text))
+    }
+
+    // Get tweets by specific author with pagination - like GET /users/{id}/tweets
+    // This demonstrates filtering in blockchain storage (no SQL WHERE clause available)
+    pub fn get_tweets_by_author(
+        &self,
+        author_id: AccountId,
+        from_index: Option<u64>,
+        limit: Option<u64>,
+        include_adult: Option<bool>,
+    ) -> Vec<Tweet> {
+        let start = from_index.unwrap_or(0);
+        let limit_val = limit.unwrap_or(10);
+
+        // We need to manually filter since blockchain storage doesn't have SQL-like queries
+        // This is like doing: SELECT * FROM tweets WHERE author = ? LIMIT x OFFSET y
+        // But we have to iterate through all tweets and filter manually
+        let mut author_tweets = Vec::new();
+        let mut count = 0;
+        let mut current_index = 0;
+
+        // Iterate through all tweets to find matches
+        for (_id, tweet) in self.tweets.iter() {
+            // Check if this tweet belongs to the requested author
+            if tweet.author == author_id && is_listed(tweet, include_adult) {
+                // Apply pagination logic
+                if current_index >= start && count < limit_val {
+                    author_tweets.push(tweet.clone());
+                    count += 1;
+                }
+                current_index += 1;
+            }
+
+            // Optimization: stop early if we've found enough tweets
+            if count >= limit_val && current_index > start {
+                break;
+            }
+        }
+
+        author_tweets
+    }
+
+    // List the curated topics registry with pagination - like GET /topics
+    pub fn get_topics(&self, from_index: Option<u32>, limit: Option<u32>) -> Vec<Topic> {
+        self.topics
+            .iter()
+            .skip(from_index.unwrap_or(0) as usize)
+            .take(limit.unwrap_or(10) as usize)
+            .cloned()
+            .collect()
+    }
+
+    // Get tweets tagged with a topic with pagination - like GET /topics/{id}/tweets
+    // Same scan-and-filter approach as get_tweets_by_author
+    pub fn get_topic_feed(
+        &self,
+        topic_id: u32,
+        from_index: Option<u64>,
+        limit: Option<u64>,
+        include_adult: Option<bool>,
+    ) -> Vec<Tweet> {
+        self.tweets
+            .values()
+            .filter(|tweet| tweet.topics.contains(&topic_id)) // WHERE topic_id = ?
+            .filter(|tweet| is_listed(tweet, include_adult))
+            .skip(from_index.unwrap_or(0) as usize)
+            .take(limit.unwrap_or(10) as usize)
+            .cloned()
+            .collect()
+    }
+
+    // Search tweets by keywords - like GET /search?q=near+rust&offset=0&limit=10
+    // All query terms must match (AND); results are returned newest first
+    // Only the most recent max_postings_per_term tweets are searchable per word
+    pub fn search_tweets(
+        &self,
+        query: String,
+        from_index: Option<u64>,
+        limit: Option<u64>,
+        include_adult: Option<bool>,
+    ) -> Vec<Tweet> {
+        let terms = tokenize(&query);
+        if terms.len() > MAX_QUERY_TERMS {
+            env::panic_str(&format!(
+                "Too many search terms: at most {} are supported",
+                MAX_QUERY_TERMS
+            ));
+        }
+
+        // Load the posting list of every term; a term nobody used means no AND matches
+        let mut postings: Vec<&Vec<TweetId>> = Vec::new();
+        for term in &terms {
+            match self.search_index.get(term) {
+                Some(tweet_ids) => postings.push(tweet_ids),
+                None => return Vec::new(),
+            }
+        }
+
+        // Walk the shortest list and check the others (sorted, so binary search is cheap)
+        // This is the classic posting-list intersection used by search engines
+        postings.sort_by_key(|tweet_ids| tweet_ids.len());
+        let Some((shortest, others)) = postings.split_first() else {
+            return Vec::new(); // Query had no searchable words (e.g. only stopwords)
+        };
+
+        shortest
+            .iter()
+            .rev() // Newest first
+            .copied()
+            .filter(|tweet_id| {
+                others
+                    .iter()
+                    .all(|tweet_ids| tweet_ids.binary_search(tweet_id).is_ok())
+            })
+            .filter_map(|tweet_id| self.tweets.get(&tweet_id)) // Skips deleted tweets
+            .filter(|tweet| is_listed(tweet, include_adult))
+            .skip(from_index.unwrap_or(0) as usize)
+            .take(limit.unwrap_or(10) as usize)
+            .cloned()
+            .collect()
+    }
+
+    // Get activity counters for a range of days (inclusive) - like GET /stats?from=..&to=..
+    // Days are numbered since Unix epoch; days without activity are returned with zeros
+    pub fn get_activity_stats(&self, from_day: u32, to_day: u32) -> Vec<DailyStats> {
+        if to_day < from_day {
+            return Vec::new();
+        }
+        if to_day - from_day >= MAX_ACTIVITY_STATS_DAYS {
+            env::panic_str(&format!(
+                "Date range too large: at most {} days per call",
+                MAX_ACTIVITY_STATS_DAYS
+            ));
+        }
+
+        (from_day..=to_day)
+            .map(|day| {
+                self.daily_stats.get(&day).cloned().unwrap_or(DailyStats {
+                    day,
+                    ..DailyStats::default()
+                })
+            })
+            .collect()
+    }
+
+    // Get an author's weekly engagement for a range of weeks (inclusive) - like
+    // GET /users/{id}/analytics?from=..&to=.. on a creator dashboard
+    // Weeks without activity are returned with zeros; persona posts count for the owner
+    // (get_persona_weekly_rollups breaks them down per persona)
+    pub fn get_weekly_rollups(
+        &self,
+        account_id: AccountId,
+        from_week: u32,
+        to_week: u32,
+    ) -> Vec<WeeklyRollup> {
+        self.rollups_for_weeks(from_week, to_week, |week| {
+            self.weekly_rollups.get(&(account_id.clone(), week))
+        })
+    }
+
+    // Get a persona's weekly engagement for a range of weeks (inclusive) - like
+    // GET /orgs/{handle}/analytics?from=..&to=..
+    // Only counts what was posted as the persona; its owner's rollups include it too
+    pub fn get_persona_weekly_rollups(
+        &self,
+        handle: String,
+        from_week: u32,
+        to_week: u32,
+    ) -> Vec<WeeklyRollup> {
+        self.rollups_for_weeks(from_week, to_week, |week| {
+            self.persona_rollups.get(&(handle.clone(), week))
+        })
+    }
+
+    // Get the tweets gaining likes fastest right now - like GET /explore/rising
+    // window: how many recent blocks of likes to count (default ~1 hour, at most ~1 day)
+    // Ties are broken by the newer tweet first, so every node returns the same order
+    pub fn get_rising_tweets(
+        &self,
+        window: Option<u64>,
+        limit: Option<u32>,
+        include_adult: Option<bool>,
+    ) -> Vec<RisingTweet> {
+        let window = window.unwrap_or(DEFAULT_RISING_WINDOW);
+        if window > RISING_BUCKETS * RISING_BUCKET_BLOCKS {
+            env::panic_str(&format!(
+                "Window too large: at most {} blocks per call",
+                RISING_BUCKETS * RISING_BUCKET_BLOCKS
+            ));
+        }
+
+        let mut rising = self.rank_rising(window, include_adult);
+        rising.truncate(limit.unwrap_or(10) as usize);
+        rising
+    }
+
+    // Get the newest tweets ordered by the configured ranking weights - like GET /home?sort=top
+    // Only the MAX_RANKED_CANDIDATES newest tweets are scored; ties go to the newer tweet,
+    // so the order only depends on contract state and the block timestamp
+    // Adult tweets are left out unless include_adult is true, like every other list view
+    pub fn get_ranked_feed(
+        &self,
+        limit: Option<u32>,
+        include_adult: Option<bool>,
+    ) -> Vec<RankedTweet> {
+        let ranking = &self.config.ranking;
+        let now = env::block_timestamp();
+
+        let candidates = self.tweet_page(None, Some(MAX_RANKED_CANDIDATES), |tweet| {
+            is_listed(tweet, include_adult)
+        });
+        let mut ranked: Vec<RankedTweet> = candidates
+            .tweets
+            .into_iter()
+            .map(|tweet| {
+                // u128 so that large weights or likes can't overflow
+                let points = 1000 + tweet.likes as u128 * ranking.like_weight as u128;
+                let half_life = ranking.recency_half_life as u128;
+                let age = now.saturating_sub(tweet.timestamp) as u128;
+                let score = (points * half_life / (half_life + age)).min(u64::MAX as u128);
+                RankedTweet {
+                    tweet,
+                    score: score as u64,
+                }
+            })
+            .collect();
+        ranked.sort_by(|a, b| {
+            b.score
+                .cmp(&a.score)
+                .then_with(|| b.tweet.id.cmp(&a.tweet.id))
+        });
+        ranked.truncate(limit.unwrap_or(10) as usize);
+        ranked
+    }
+
+    // Get everything the explore page shows in one call - like a GET /explore BFF endpoint
+    // that aggregates several backend services, so simple frontends make one RPC call
+    // Adult tweets are left out unless include_adult is true, like every other list view
+    pub fn get_explore(&self, limit: Option<u32>, include_adult: Option<bool>) -> ExplorePage {
+        let limit = limit.unwrap_or(10) as usize;
+        let mut rising = self.rank_rising(DEFAULT_RISING_WINDOW, include_adult);
+
+        // Trending topics: recent likes of the rising tweets, summed per topic
+        let mut topic_likes: Vec<(u32, u64)> = Vec::new();
+        for entry in &rising {
+            for &topic_id in &entry.tweet.topics {
+                match topic_likes.iter_mut().find(|(id, _)| *id == topic_id) {
+                    Some((_, likes)) => *likes += entry.recent_likes,
+                    None => topic_likes.push((topic_id, entry.recent_likes)),
+                }
+            }
+        }
+        topic_likes.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        let trending_topics = topic_likes
+            .into_iter()
+            .take(limit)
+            .filter_map(|(topic_id, recent_likes)| {
+                Some(TrendingTopic {
+                    topic: self.topics.get(topic_id)?.clone(),
+                    recent_likes,
+                })
+            })
+            .collect();
+
+        rising.truncate(limit);
+        ExplorePage {
+            rising,
+            trending_topics,
+        }
+    }
+
+    // Get the current contract settings - like GET /config
+    pub fn get_config(&self) -> Config {
+        self.config.clone()
+    }
+
+    // Get the current rate limit (None = disabled) - like the X-RateLimit-* policy of an API
+    pub fn get_rate_limit_config(&self) -> Option<RateLimitConfig> {
+        self.config.rate_limit.clone()
+    }
+
+    // Get rate limiter counters - like GET /metrics of a rate-limiting proxy
+    pub fn get_rate_limit_metrics(&self) -> RateLimitMetrics {
+        RateLimitMetrics {
+            tracked_accounts: self.rate_limited_accounts,
+            metered_writes: self.metered_writes,
+        }
+    }
+
+    // ============================================================================================
+    // V2 READ METHODS (cursor pagination)
+    // ============================================================================================
+    // Like a /v2/ API prefix: new integrations should use these, while the v1 methods above
+    // keep their offset-based behavior so existing integrations don't break.
+    // A cursor is the id of the last tweet you received; pages are newest first and walk tweet
+    // ids downwards, so tweets deleted between pages never cause skips or duplicates
+
+    // Get the global timeline - like GET /v2/tweets?cursor=<id>&limit=10
+    pub fn get_tweets_v2(
+        &self,
+        cursor: Option<TweetId>,
+        limit: Option<u64>,
+        include_adult: Option<bool>,
+    ) -> TweetPage {
+        self.tweet_page(cursor, limit, |tweet| is_listed(tweet, include_adult))
+    }
+
+    // Get one author's tweets - like GET /v2/users/{id}/tweets?cursor=<id>&limit=10
+    pub fn get_tweets_by_author_v2(
+        &self,
+        author_id: AccountId,
+        cursor: Option<TweetId>,
+        limit: Option<u64>,
+        include_adult: Option<bool>,
+    ) -> TweetPage {
+        self.tweet_page(cursor, limit, |tweet| {
+            tweet.author == author_id && is_listed(tweet, include_adult)
+        })
+    }
+
+    // ============================================================================================
+    // DATA PORTABILITY
+    // ============================================================================================
+
+    // Page through everything the contract stores about an account - like "download your data"
+    // Sections (see EXPORT_SECTIONS):
+    // - "tweets": tweets the account authored or co-authored, newest first, paged by cursor
+    //   (a tweet id, like get_tweets_v2); a page may be short or empty while next_cursor is set
+    // - "rsvps": the account's event answers, newest event first, paged by event tweet id the
+    //   same way (answers to events deleted since are included too)
+    // - "settings", "posting_delegates", "linked_identities", "personas" (owned),
+    //   "short_links": small, returned in one page
+    // All contract state is public, so this is a view that takes the account id explicitly
+    pub fn export_my_data(
+        &self,
+        account_id: AccountId,
+        section: String,
+        cursor: Option<TweetId>,
+        limit: Option<u64>,
+    ) -> ExportPage {
+        use near_sdk::serde_json::to_value;
+
+        let to_record = |value: Result<near_sdk::serde_json::Value, _>| {
+            value.expect("Stored data is serializable")
+        };
+        let (records, next_cursor) = match section.as_str() {
+            "tweets" => {
+                let page = self.tweet_page(cursor, limit, |tweet| {
+                    tweet.author == account_id || tweet.coauthors.contains(&account_id)
+                });
+                let records = page.tweets.iter().map(|tweet| to_record(to_value(tweet)));
+                (records.collect(), page.next_cursor)
+            }
+            "settings" => (
+                vec![to_record(to_value(self.get_settings(account_id.clone())))],
+                None,
+            ),
+            "posting_delegates" => {
+                let delegations = self.get_posting_delegates(account_id.clone());
+                let records = delegations
+                    .iter()
+                    .map(|delegation| to_record(to_value(delegation)));
+                (records.collect(), None)
+            }
+            "linked_identities" => {
+                let identities = self.get_linked_identities(account_id.clone());
+                let records = identities
+                    .iter()
+                    .map(|identity| to_record(to_value(identity)));
+                (records.collect(), None)
+            }
+            "personas" => {
+                let handles = self.personas_by_owner.get(&account_id);
+                let records = handles
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|handle| self.personas.get(handle))
+                    .map(|persona| to_record(to_value(persona)));
+                (records.collect(), None)
+            }
+            "short_links" => {
+                let codes = self.short_links_by_owner.get(&account_id);
+                let records = codes
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|code| self.short_links.get(code))
+                    .map(|link| to_record(to_value(link)));
+                (records.collect(), None)
+            }
+            "rsvps" => {
+                // Walk event ids downwards like tweet_page; an Rsvp doesn't name its event,
+                // so each record gets the event's tweet_id added
+                let limit = limit.unwrap_or(10);
+                let mut next_id = cursor.map_or(self.next_tweet_id, |cursor| {
+                    cursor.0.min(self.next_tweet_id)
+                });
+                let scan_end = next_id.saturating_sub(MAX_PAGE_SCAN);
+                let mut records = Vec::new();
+                while next_id > scan_end && (records.len() as u64) < limit {
+                    next_id -= 1;
+                    let tweet_id = TweetId(next_id);
+                    if let Some(rsvp) = self.rsvps.get(&(tweet_id, account_id.clone())) {
+                        let mut record = to_record(to_value(rsvp));
+                        record["tweet_id"] = to_record(to_value(tweet_id));
+                        records.push(record);
+                    }
+                }
+                (records, (next_id > 0).then_some(TweetId(next_id)))
+            }
+            _ => env::panic_str(&format!(
+                "Unknown export section: {} (expected one of: {})",
+                section,
+                EXPORT_SECTIONS.join(", ")
+            )),
+        };
+
+        ExportPage {
+            schema_version: EXPORT_SCHEMA_VERSION,
+            section,
+            records,
+            next_cursor,
+        }
+    }
+
+    // ============================================================================================
+    // ACTIVITYPUB VIEWS (ActivityStreams JSON for federation bridges)
+    // ============================================================================================
+    // Like exposing /users/{id} and /tweets/{id} with "Accept: application/activity+json"
+
+    // Get a tweet as an ActivityStreams Note - None if the tweet doesn't exist
+    pub fn get_activitypub_note(&self, tweet_id: TweetId) -> Option<ActivityNote> {
+        self.tweets
+            .get(&tweet_id)
+            .map(|tweet| activity_note(tweet, Some(ACTIVITYSTREAMS_CONTEXT.to_string())))
+    }
+
+    // Get an account as an ActivityStreams Person
+    pub fn get_activitypub_person(&self, account_id: AccountId) -> ActivityPerson {
+        ActivityPerson {
+            context: ACTIVITYSTREAMS_CONTEXT.to_string(),
+            id: activitypub_person_id(&account_id),
+            kind: "Person".to_string(),
+            preferred_username: account_id.to_string(),
+            attachment: self
+                .get_linked_identities(account_id)
+                .into_iter()
+                .map(|identity| ActivityPropertyValue {
+                    kind: "PropertyValue".to_string(),
+                    name: identity.platform.name().to_string(),
+                    value: identity.handle,
+                })
+                .collect(),
+        }
+    }
+
+    // Get the Create activity of a tweet - what a bridge delivers to followers' inboxes
+    pub fn get_activitypub_create(&self, tweet_id: TweetId) -> Option<ActivityCreate> {
+        self.tweets.get(&tweet_id).map(|tweet| {
+            let note = activity_note(tweet, None);
+            ActivityCreate {
+                context: ACTIVITYSTREAMS_CONTEXT.to_string(),
+                id: format!("{}/activity", note.id),
+                kind: "Create".to_string(),
+                actor: note.attributed_to.clone(),
+                published: note.published.clone(),
+                to: note.to.clone(),
+                object: note,
+            }
+        })
+    }
+
+    // Validate internal consistency of the contract state - run it after migrations
+    // Checks counters against collection sizes and, for a random sample of up to
+    // `sample_size` tweets, that each tweet agrees with the time-ordered index and that the
+    // search index entries of its words point to real tweets
+    // The cleanup queue and pending drafts are checked `sample_size` entries at a time from
+    // `from_index`, so a large state never hits the view gas limit: repeat with next_index
+    pub fn check_invariants(&self, sample_size: u32, from_index: Option<u32>) -> InvariantReport {
+        let sample_size = sample_size.min(MAX_INVARIANT_SAMPLE_SIZE);
+        let from_index = from_index.unwrap_or(0);
+        let mut violations = Vec::new();
+
+        // Counters vs collection sizes
+        if u64::from(self.tweet_timestamps.len()) != self.next_tweet_id {
+            violations.push(format!(
+                "Time index has {} entries but next_tweet_id is {}",
+                self.tweet_timestamps.len(),
+                self.next_tweet_id
+            ));
+        }
+        if u64::from(self.tweets.len()) > self.next_tweet_id {
+            violations.push(format!(
+                "{} tweets stored but only {} ids were ever assigned",
+                self.tweets.len(),
+                self.next_tweet_id
+            ));
+        }
+        if self.next_op_id > 0 && self.operations.get(&(self.next_op_id - 1)).is_none() {
+            violations.push(format!(
+                "Latest operation #{} is missing",
+                self.next_op_id - 1
+            ));
+        }
+
+        // Cleanup queue must only reference deleted tweets
+        for (tweet_id, _) in self
+            .gc_queue
+            .iter()
+            .skip(from_index as usize)
+            .take(sample_size as usize)
+        {
+            if self.tweets.contains_key(tweet_id) {
+                violations.push(format!("GC queue references live tweet #{}", tweet_id));
+            }
+        }
+
+        // Pending drafts must still be waiting for someone
+        for (draft_id, draft) in self
+            .drafts
+            .iter()
+            .skip(from_index as usize)
+            .take(sample_size as usize)
+        {
+            if draft.approvals.len() >= draft.coauthors.len() {
+                violations.push(format!(
+                    "Draft #{} is fully approved but unpublished",
+                    draft_id
+                ));
+            }
+            if draft
+                .approvals
+                .iter()
+                .any(|account| !draft.coauthors.contains(account))
+            {
+                violations.push(format!(
+                    "Draft #{} has approvals from non-co-authors",
+                    draft_id
+                ));
+            }
+        }
+
+        let next_index = from_index.saturating_add(sample_size);
+        let next_index =
+            (next_index < self.gc_queue.len().max(self.drafts.len())).then_some(next_index);
+
+        // Random sample of tweets: start at a random position (block randomness is fine here)
+        let start = if self.tweets.is_empty() {
+            0
+        } else {
+            let seed = env::random_seed_array();
+            u32::from_le_bytes([seed[0], seed[1], seed[2], seed[3]]) % self.tweets.len()
+        };
+        let mut tweets_checked = 0;
+        let mut terms_checked = HashSet::new();
+        for (tweet_id, tweet) in self
+            .tweets
+            .iter()
+            .skip(start as usize)
+            .chain(self.tweets.iter().take(start as usize)) // Wrap around to the beginning
+            .take(sample_size as usize)
+        {
+            tweets_checked += 1;
+            if tweet.id != *tweet_id {
+                violations.push(format!(
+                    "Tweet stored under #{} has id #{}",
+                    tweet_id, tweet.id
+                ));
+            }
+            match u32::try_from(tweet_id.0)
+                .ok()
+                .and_then(|index| self.tweet_timestamps.get(index))
+            {
+                Some(timestamp) if *timestamp == tweet.timestamp => {}
+                _ => violations.push(format!(
+                    "Tweet #{} is missing from the time index or has a different timestamp",
+                    tweet_id
+                )),
+            }
+            if tweet
+                .topics
+                .iter()
+                .any(|topic_id| *topic_id >= self.topics.len())
+            {
+                violations.push(format!("Tweet #{} references an unknown topic", tweet_id));
+            }
+
+            // Search index -> tweets: postings of deleted tweets are fine while their cleanup
+            // is queued (see gc_indexes), but never-posted ids are always corrupt
+            let terms = match tweet.storage_tier {
+                StorageTier::OffChain { .. } => self
+                    .offchain_terms
+                    .get(tweet_id)
+                    .cloned()
+                    .unwrap_or_default(),
+                StorageTier::OnChain => tokenize(&tweet.text),
+            };
+            for term in terms {
+                if !terms_checked.insert(term.clone()) {
+                    continue; // Another sampled tweet shares this word
+                }
+                for posting in self.search_index.get(&term).into_iter().flatten() {
+                    if posting.0 >= self.next_tweet_id {
+                        violations.push(format!(
+                            "Search index entry '{}' references tweet #{} that was never posted",
+                            term, posting
+                        ));
+                    } else if self.gc_queue.is_empty() && !self.tweets.contains_key(posting) {
+                        violations.push(format!(
+                            "Search index entry '{}' references deleted tweet #{} with no cleanup queued",
+                            term, posting
+                        ));
+                    }
+                }
+            }
+        }
+
+        InvariantReport {
+            tweets_checked,
+            next_index,
+            violations,
+        }
+    }
+
+    // List state snapshots with pagination, oldest first - like GET /audit/snapshots
+    pub fn get_snapshots(&self, from_index: Option<u32>, limit: Option<u32>) -> Vec<Snapshot> {
+        self.snapshots
+            .iter()
+            .skip(from_index.unwrap_or(0) as usize)
+            .take(limit.unwrap_or(10) as usize)
+            .cloned()
+            .collect()
+    }
+
+    // Prove that a tweet was among the first `tree_size` tweets (default: all of them)
+    // Passing a snapshot's next_tweet_id gives a proof against that snapshot's tweets_root
+    // Works for deleted tweets too: the leaf stays, only the tweet data is gone
+    pub fn get_inclusion_proof(
+        &self,
+        tweet_id: TweetId,
+        tree_size: Option<u64>,
+    ) -> Option<InclusionProof> {
+        let tree_size = tree_size.unwrap_or(self.next_tweet_id);
+        if tweet_id.0 >= tree_size || tree_size > self.next_tweet_id {
+            return None;
+        }
+
+        // Find the perfect subtree (peak) covering the leaf: peaks are ordered by size,
+        // largest first, following the binary representation of tree_size
+        let peaks = self.tree_peaks(tree_size);
+        let mut peak_start = 0;
+        let mut peak_index = 0;
+        let mut peak_height = 0;
+        for height in (0..64u8).rev() {
+            if tree_size & (1 << height) == 0 {
+                continue;
+            }
+            if tweet_id.0 < peak_start + (1 << height) {
+                peak_height = height;
+                break;
+            }
+            peak_start += 1 << height;
+            peak_index += 1;
+        }
+
+        let path = (0..peak_height)
+            .map(|height| {
+                let index = tweet_id.0 >> height;
+                ProofStep {
+                    sibling: self.tree_node(height, index ^ 1).to_vec().into(),
+                    sibling_is_left: index % 2 == 1,
+                }
+            })
+            .collect();
+
+        Some(InclusionProof {
+            tweet_id,
+            tree_size,
+            leaf_hash: self.tree_node(0, tweet_id.0).to_vec().into(),
+            path,
+            root: merkle_root(&peaks),
+            peaks: peaks.iter().map(|peak| peak.to_vec().into()).collect(),
+            peak_index,
+        })
+    }
+
+    // Get the contract status - like GET /status used by load balancers for readiness checks
+    // Frontends can call this to disable the "Tweet" button during maintenance
+    pub fn get_status(&self) -> ContractStatus {
+        ContractStatus {
+            contract_version: env!("CARGO_PKG_VERSION").to_string(),
+            state_schema_version: STATE_SCHEMA_VERSION,
+            mode: self.mode,
+            decommission_after: self.decommission_after,
+            decommission_progress: self.decommission_progress.clone(),
+            config_digest: env::sha256(
+                &near_sdk::borsh::to_vec(&self.config).expect("Config is serializable"),
+            )
+            .into(),
+            gc_pending: self.gc_queue.len(),
+            expiry_cursor: self.expiry_cursor,
+            migration_pending: self
+                .legacy_migration
+                .as_ref()
+                .map_or(0, |migration| migration.end_id - migration.next_id),
+        }
+    }
+
+    // Get usage counters of every write method - like per-route request counts in an APM tool
+    // Gives operators basic usage telemetry without running an indexer
+    pub fn get_method_metrics(&self) -> Vec<MethodMetric> {
+        API_METHODS
+            .iter()
+            .filter(|method| method.kind != "view")
+            .map(|method| MethodMetric {
+                method: method.name.to_string(),
+                kind: method.kind.to_string(),
+                calls: self.method_calls.get(method.name).copied().unwrap_or(0),
+            })
+            .collect()
+    }
+
+    // Describe the public API - like GET /openapi.json
+    // Client SDKs and explorers can generate bindings from this instead of reading the source
+    pub fn get_api_spec(&self) -> ApiSpec {
+        ApiSpec {
+            contract_version: env!("CARGO_PKG_VERSION").to_string(),
+            methods: API_METHODS
+                .iter()
+                .map(|method| ApiMethod {
+                    name: method.name.to_string(),
+                    kind: method.kind.to_string(),
+                    args: method.args.iter().map(|arg| arg.to_string()).collect(),
+                    pagination: method.pagination.map(str::to_string),
+                })
+                .collect(),
+            errors: API_ERRORS.iter().map(|error| error.to_string()).collect(),
+        }
+    }
+}
+
+// ================================================================================================
+// DEMO DATA (only compiled with `cargo near build --features demo-seed`)
+// ================================================================================================
+// Like a `rails db:seed` task: fills a sandbox deployment with realistic-looking content so
+// frontends, demos and load tests have something to show right away.
+// It is never part of production builds, so it can't be called on mainnet.
+#[cfg(feature = "demo-seed")]
+#[near]
+impl TwitterContract {
+    // Populate demo state - owner only, returns the number of tweets created
+    // The output is deterministic: the same arguments always produce the same authors
+    // (user0.<contract>, user1.<contract>, ...), texts, co-authored tweets and like counts
+    pub fn seed_demo_data(&mut self, num_accounts: u32, num_tweets: u32) -> u32 {
+        self.assert_owner();
+        self.assert_content_creation_allowed();
+        if num_accounts == 0 || num_accounts > MAX_DEMO_ACCOUNTS {
+            env::panic_str(&format!(
+                "num_accounts must be between 1 and {}",
+                MAX_DEMO_ACCOUNTS
+            ));
+        }
+        if num_tweets > MAX_DEMO_TWEETS {
+            env::panic_str(&format!("num_tweets must be at most {}", MAX_DEMO_TWEETS));
+        }
+
+        let accounts: Vec<AccountId> = (0..num_accounts)
+            .map(|index| {
+                format!("user{}.{}", index, env::current_account_id())
+                    .parse()
+                    .unwrap_or_else(|_| {
+                        env::panic_str("Contract account id is too long for demo sub-accounts")
+                    })
+            })
+            .collect();
+
+        for index in 0..num_tweets as usize {
+            let author = accounts[index % accounts.len()].clone();
+            // Every fifth tweet is co-written with the next demo account
+            let coauthors = if index % 5 == 4 && accounts.len() > 1 {
+                vec![accounts[(index + 1) % accounts.len()].clone()]
+            } else {
+                Vec::new()
+            };
+            let text = DEMO_TEXTS[index % DEMO_TEXTS.len()].to_string();
+            let tweet =
+                self.insert_tweet(author, coauthors, None, text, None, None, Vec::new(), None);
+
+            // Uneven engagement, like a real feed: most tweets get a few likes, some get many
+            let likes = (index as u64 * 7 + 3) % (num_accounts as u64 + 1);
+            self.tweets
+                .get_mut(&tweet.id)
+                .expect("Tweet was just stored")
+                .likes = likes;
+            self.todays_stats().likes += likes;
+            self.this_weeks_rollup(&tweet.author).likes_received += likes;
+        }
+
+        env::log_str(&format!(
+            "Seeded {} demo tweets from {} accounts",
+            num_tweets, num_accounts
+        ));
+        num_tweets
+    }
+}
+
+// ================================================================================================
+// INTERNAL HELPERS
+// ================================================================================================
+
+// Canonical form of tweet text used for hashing, so that copies which only differ in
+// insignificant ways still verify: line endings are normalized to "\n" and leading/trailing
+// whitespace is removed. Off-chain tools must apply the same rules before hashing.
+fn canonicalize_text(text: &str) -> String {
+    text.replace("\r\n", "\n")
+        .replace('\r', "\n")
+        .trim()
+        .to_string()
+}
+
+// Week number of the current block (block_timestamp / 7 days, see WeeklyRollup)
+fn current_week() -> u32 {
+    (env::block_timestamp() / (7 * NANOS_PER_DAY)) as u32
+}
+
+// Split text into search terms: lowercase words of reasonable length, without stopwords
+// Each distinct word is returned once, in order of first appearance (e.g. "#NEAR" -> "near")
+fn tokenize(text: &str) -> Vec<String> {
+    let mut terms: Vec<String> = Vec::new();
+    for word in text.split(|c: char| !c.is_alphanumeric()) {
+        let length = word.chars().count();
+        if !(MIN_TERM_LEN..=MAX_TERM_LEN).contains(&length) {
+            continue;
+        }
+
+        let term = word.to_lowercase();
+        if STOPWORDS.contains(&term.as_str()) || terms.contains(&term) {
+            continue;
+        }
+
+        terms.push(term);
+        if terms.len() == MAX_TERMS_PER_TWEET {
+            break;
+        }
+    }
+    terms
+}
+
+// Off-chain pointers must use a content-addressed scheme and have a bounded length
+fn assert_valid_content_uri(uri: &str) {
+    if !(uri.starts_with("ipfs://") || uri.starts_with("ar://")) {
+        env::panic_str("content_uri must start with ipfs:// or ar://");
+    }
+    if uri.len() > MAX_CONTENT_URI_LEN {
+        env::panic_str("content_uri is too long");
+    }
+}
+
+// SHA-256 of the canonicalized text - env::sha256 runs natively in the NEAR runtime (cheap gas)
+fn content_hash(text: &str) -> Base64VecU8 {
+    env::sha256(canonicalize_text(text).as_bytes()).into()
+}
+
+// Whether a list view returns this tweet: age-restricted tweets need an explicit opt-in
+// (views can't tell who is reading, so the caller's choice has to be a parameter)
+fn is_listed(tweet: &Tweet, include_adult: Option<bool>) -> bool {
+    !tweet.adult || include_adult.unwrap_or(false)
+}
+
+// Accumulator leaf of a tweet: commits to its id, post time, content and author
+// (0x00 / 0x01 prefixes keep leaves and inner nodes from ever being confused)
+fn tweet_leaf_hash(tweet: &Tweet) -> [u8; 32] {
+    let mut data = vec![0u8];
+    data.extend(tweet.id.0.to_le_bytes());
+    data.extend(tweet.timestamp.to_le_bytes());
+    data.extend(&tweet.content_hash.0);
+    data.extend(tweet.author.as_str().as_bytes());
+    env::sha256_array(&data)
+}
+
+// Inner accumulator node over two children
+fn merkle_node(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut data = vec![1u8];
+    data.extend(left);
+    data.extend(right);
+    env::sha256_array(&data)
+}
+
+// Single root committing to all peaks (sha256 of their concatenation)
+fn merkle_root(peaks: &[[u8; 32]]) -> Base64VecU8 {
+    env::sha256(&peaks.concat()).into()
+}
+
+// Base62-encode a short link id: 0 -> "0", 61 -> "Z", 62 -> "10", ...
+// Ids are unique, so the codes are too, and they stay short (3 characters up to ~238k links)
+fn short_link_code(mut id: u64) -> String {
+    let base = SHORT_LINK_ALPHABET.len() as u64;
+    let mut code = Vec::new();
+    loop {
+        code.push(SHORT_LINK_ALPHABET[(id % base) as usize]);
+        id /= base;
+        if id == 0 {
+            break;
+        }
+    }
+    code.reverse();
+    String::from_utf8(code).expect("The alphabet is ASCII")
+}
+
+// Build a JSON object with only the given fields of a tweet (names must be in TWEET_FIELDS)
+fn select_tweet_fields(tweet: &Tweet, fields: &[String]) -> near_sdk::serde_json::Value {
+    use near_sdk::serde_json::{json, Map};
+
+    let mut object = Map::new();
+    for field in fields {
+        let value = match field.as_str() {
+            "id" => json!(tweet.id),
+            "author" => json!(tweet.author),
+            "text" => json!(tweet.text),
+            "timestamp" => json!(tweet.timestamp),
+            "likes" => json!(tweet.likes),
+            "license" => json!(tweet.license),
+            "content_hash" => json!(tweet.content_hash),
+            "storage_tier" => json!(tweet.storage_tier),
+            "topics" => json!(tweet.topics),
+            "coauthors" => json!(tweet.coauthors),
+            "signer" => json!(tweet.signer),
+            "imported" => json!(tweet.imported),
+            "original_created_at" => json!(tweet.original_created_at),
+            "frozen" => json!(tweet.frozen),
+            "adult" => json!(tweet.adult),
+            "persona" => json!(tweet.persona),
+            _ => continue,
+        };
+        object.insert(field.clone(), value);
+    }
+    object.into()
+}
+
+// ActivityPub id of an account's Person object
+fn activitypub_person_id(account_id: &AccountId) -> String {
+    format!("near://{}/users/{}", env::current_account_id(), account_id)
+}
+
+// Build the ActivityStreams Note of a tweet (context is only set on top-level objects)
+fn activity_note(tweet: &Tweet, context: Option<String>) -> ActivityNote {
+    let url = match &tweet.storage_tier {
+        StorageTier::OnChain => None,
+        StorageTier::OffChain { uri } => Some(uri.clone()),
+    };
+    ActivityNote {
+        context,
+        id: format!("near://{}/tweets/{}", env::current_account_id(), tweet.id),
+        kind: "Note".to_string(),
+        attributed_to: activitypub_person_id(&tweet.author),
+        content: tweet.text.clone(),
+        // Imported tweets keep their original publication time
+        published: format_rfc3339(tweet.original_created_at.unwrap_or(tweet.timestamp)),
+        to: vec![ACTIVITYSTREAMS_PUBLIC.to_string()],
+        url,
+    }
+}
+
+// Format a block timestamp (nanoseconds since Unix epoch) as RFC 3339 in UTC
+// No date library is available in contracts, so this uses the classic days-to-civil algorithm
+fn format_rfc3339(timestamp: Timestamp) -> String {
+    let seconds = timestamp / 1_000_000_000;
+    let days = (seconds / 86_400) as i64;
+    let seconds_of_day = seconds % 86_400;
+
+    // Convert days since 1970-01-01 to a (year, month, day) date
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let day_of_era = z - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        seconds_of_day / 3_600,
+        seconds_of_day % 3_600 / 60,
+        seconds_of_day % 60
+    )
+}
+
+// Methods in a plain impl block (without #[near]) are not exposed as contract methods
+// Think of these as private helper functions of your service class
+impl TwitterContract {
+    // Load a persona for an owner-only method (like checking org ownership in a handler)
+    fn assert_persona_owner(&mut self, handle: &String) -> &mut Persona {
+        let persona = self
+            .personas
+            .get_mut(handle)
+            .unwrap_or_else(|| env::panic_str("Persona not found"));
+        if persona.owner != env::predecessor_account_id() {
+            env::panic_str("Only the persona owner can manage it");
+        }
+        persona
+    }
+
+    // Load a live space for a host-only method (like checking resource ownership in a handler)
+    fn assert_space_host(&mut self, space_id: u64) -> &mut Space {
+        let caller = env::predecessor_account_id();
+        let space = self
+            .live_spaces
+            .get_mut(&space_id)
+            .unwrap_or_else(|| env::panic_str("Space not found or already ended"));
+        if space.host != caller {
+            env::panic_str("Only the host can manage this space");
+        }
+        space
+    }
 
-            // Start tweet IDs from 0
-            next_tweet_id: 0,
+    // Authorization check for admin-only methods (like an "admin" role middleware)
+    // Panicking reverts the whole transaction, so no state changes are persisted
+    fn assert_owner(&self) {
+        if env::predecessor_account_id() != env::current_account_id() {
+            env::panic_str("Only the contract owner can call this method");
+        }
+        // With an admin council, a transaction signed with the contract account's own key is
+        // refused: owner methods then only run as calls dispatched by approved proposals
+        if self.admin_council.is_some() && env::signer_account_id() == env::current_account_id() {
+            env::panic_str("Owner methods need admin council approval: use propose_admin_action");
         }
     }
 
-    // ============================================================================================
-    // WRITE METHODS (Cost gas, modify state)
-    // ============================================================================================
+    // Guard for the council's own methods - returns the calling member
+    fn assert_council_member(&self) -> AccountId {
+        let caller = env::predecessor_account_id();
+        let is_member = self
+            .admin_council
+            .as_ref()
+            .is_some_and(|council| council.members.contains(&caller));
+        if !is_member {
+            env::panic_str("Only admin council members can propose or approve admin actions");
+        }
+        caller
+    }
 
-    // Post a new tweet - equivalent to POST /tweets endpoint
-    // This is a "call" method that modifies state and costs gas
-    pub fn post_tweet(&mut self, text: String) -> Tweet {
-        // Get the account that called this method (like extracting user from JWT token)
-        // env::predecessor_account_id() returns who made the transaction
-        let author = env::predecessor_account_id();
+    // Store an admin proposal, first dispatching its call if it has enough approvals
+    // The call goes from the contract to itself, so assert_owner lets it through, and
+    // on_admin_proposal_executed records whether it succeeded
+    fn settle_admin_proposal(&mut self, mut proposal: AdminProposal) -> AdminProposal {
+        let threshold = self
+            .admin_council
+            .as_ref()
+            .map_or(usize::MAX, |council| council.threshold as usize);
+        if proposal.approvals.len() >= threshold {
+            proposal.status = AdminProposalStatus::Executing;
+            env::log_str(&format!(
+                "Admin proposal #{} approved: calling {}",
+                proposal.id, proposal.method
+            ));
+            let callback_args = near_sdk::serde_json::to_vec(&near_sdk::serde_json::json!({
+                "proposal_id": proposal.id,
+            }))
+            .expect("Proposal id is serializable");
+            Promise::new(env::current_account_id())
+                .function_call(
+                    proposal.method.clone(),
+                    proposal.args.clone().into_bytes(),
+                    NearToken::from_yoctonear(0),
+                    ADMIN_PROPOSAL_CALL_GAS,
+                )
+                .then(Promise::new(env::current_account_id()).function_call(
+                    "on_admin_proposal_executed".to_string(),
+                    callback_args,
+                    NearToken::from_yoctonear(0),
+                    ADMIN_PROPOSAL_CALLBACK_GAS,
+                ));
+        }
+        self.admin_proposals.insert(proposal.id, proposal.clone());
+        proposal
+    }
+
+    // Guard for methods that create new content
+    fn assert_content_creation_allowed(&self) {
+        self.assert_not_decommissioning();
+        if self.mode == ContractMode::ReadOnly {
+            env::panic_str("Contract is in read-only mode: new content is disabled");
+        }
+    }
+
+    // Build and store a new tweet - shared by post_tweet and co-authored publishing
+    // Callers are responsible for authorization and topic validation
+    // original_created_at is only set for tweets imported from an archive
+    #[allow(clippy::too_many_arguments)]
+    fn insert_tweet(
+        &mut self,
+        author: AccountId,
+        coauthors: Vec<AccountId>,
+        signer: Option<AccountId>,
+        text: String,
+        license: Option<License>,
+        content_uri: Option<String>,
+        topics: Vec<u32>,
+        original_created_at: Option<Timestamp>,
+    ) -> Tweet {
+        // New ids must come after every legacy id (see migrate_legacy_tweets)
+        if self.legacy_migration.is_some() {
+            env::panic_str("Legacy tweets are still being migrated");
+        }
 
         // Get current blockchain timestamp (like System.currentTimeMillis() in Java)
         // NEAR provides nanoseconds since Unix epoch
         let timestamp = env::block_timestamp();
 
         // Generate unique ID for this tweet (like auto-increment primary key)
-        let tweet_id = self.next_tweet_id;
+        let tweet_id = TweetId(self.next_tweet_id);
+
+        // Fall back to the author's default license when the caller didn't pick one
+        let license = license.or_else(|| {
+            self.settings
+                .get(&author)
+                .and_then(|settings| settings.default_license)
+        });
+
+        // Hash and tokenize the full text first - both are kept even if the text itself isn't
+        let hash = content_hash(&text);
+        let terms = tokenize(&text);
+
+        // Long tweets can be kept off-chain to save storage costs: only the hash and a pointer
+        // are stored, like keeping large blobs in S3 and just a URL + checksum in the database
+        let (text, storage_tier) = match self.config.max_onchain_bytes {
+            Some(max_bytes) if text.len() > max_bytes as usize => {
+                let uri = content_uri.unwrap_or_else(|| {
+                    env::panic_str("Tweet exceeds max_onchain_bytes: content_uri is required")
+                });
+                assert_valid_content_uri(&uri);
+                (String::new(), StorageTier::OffChain { uri })
+            }
+            // Short enough: store the text on-chain (any content_uri is not needed)
+            _ => (text, StorageTier::OnChain),
+        };
 
         // Create the tweet object (like building your entity/model)
         let new_tweet = Tweet {
@@ -130,11 +5355,39 @@ impl TwitterContract {
             text,
             timestamp,
             likes: 0, // New tweets start with 0 likes
+            license,
+            content_hash: hash,
+            storage_tier,
+            topics,
+            coauthors,
+            signer,
+            imported: original_created_at.is_some(),
+            original_created_at,
+            frozen: false,
+            adult: false,
+            persona: None,
         };
 
         // Store the tweet in our "database" (contract storage)
         // This is like INSERT INTO tweets (...) VALUES (...)
         self.tweets.insert(tweet_id, new_tweet.clone());
+        self.tweet_timestamps.push(timestamp);
+        self.append_tree_leaf(tweet_id, tweet_leaf_hash(&new_tweet));
+
+        // Make the tweet findable by keyword (like updating a full-text index on INSERT)
+        // Off-chain tweets keep their terms aside, since there is no text to tokenize later
+        if matches!(new_tweet.storage_tier, StorageTier::OffChain { .. })
+            && self.config.max_postings_per_term > 0
+            && !terms.is_empty()
+        {
+            self.offchain_terms.insert(tweet_id, terms.clone());
+        }
+        self.index_terms(tweet_id, terms);
+
+        // Update today's activity counters (like incrementing metrics in a rollup table)
+        self.track_account(&author);
+        self.todays_stats().tweets_posted += 1;
+        self.this_weeks_rollup(&author).posts += 1;
 
         // Increment ID counter for next tweet (like auto-increment)
         self.next_tweet_id += 1;
@@ -146,142 +5399,638 @@ impl TwitterContract {
             tweet_id, author, timestamp
         ));
 
+        // Forward the tweet to peer communities, if federation is configured
+        self.federate(&new_tweet);
+        self.emit_event(TwitterEvent::TweetPosted, tweet_id, &author);
+
         // Return the created tweet (like returning the entity in REST API)
         new_tweet
     }
 
-    // Like a tweet - equivalent to POST /tweets/{id}/like endpoint
-    // This modifies state (increments like counter) so it costs gas
-    pub fn like_tweet(&mut self, tweet_id: u64) -> Option<Tweet> {
-        // Try to get a mutable reference to the tweet
-        // This is like: SELECT * FROM tweets WHERE id = ? FOR UPDATE
-        if let Some(tweet) = self.tweets.get_mut(&tweet_id) {
-            // Increment the like counter (like UPDATE tweets SET likes = likes + 1)
-            tweet.likes += 1;
+    // Send a summary of a new tweet to every federation peer (fire-and-forget)
+    // Each peer gets a separate cross-contract call; a failing peer doesn't affect the others
+    // or the tweet itself, since the calls run after this transaction's state is committed
+    // No gas is reserved up front: the peers split whatever gas the caller has left over
+    fn federate(&self, tweet: &Tweet) {
+        if self.config.federation_peers.is_empty() {
+            return;
+        }
 
-            // Log the like action for transparency/debugging
-            env::log_str(&format!(
-                "Tweet #{} liked by @{}. Total likes: {}",
-                tweet_id,
-                env::predecessor_account_id(), // Who liked the tweet
-                tweet.likes
-            ));
+        let post = FederatedPost {
+            origin: env::current_account_id(),
+            tweet_id: tweet.id,
+            author: tweet.author.clone(),
+            text: tweet.text.clone(),
+            content_hash: tweet.content_hash.clone(),
+            timestamp: tweet.timestamp,
+        };
+        let args = near_sdk::serde_json::to_vec(&near_sdk::serde_json::json!({ "post": post }))
+            .expect("FederatedPost is serializable");
 
-            // Return the updated tweet (clone because we need to return owned data)
-            Some(tweet.clone())
-        } else {
-            // Tweet doesn't exist - log the attempt
-            // In REST API, this would be a 404 Not Found
-            env::log_str(&format!(
-                "Attempt to like non-existent tweet #{} by @{}",
-                tweet_id,
-                env::predecessor_account_id()
+        for peer in &self.config.federation_peers {
+            Promise::new(peer.clone()).function_call_weight(
+                "receive_federated_post".to_string(),
+                args.clone(),
+                NearToken::from_yoctonear(0),
+                Gas::from_gas(0),
+                GasWeight(1),
+            );
+        }
+    }
+
+    // Emit a NEP-297 event log and notify subscribed listener contracts
+    // Indexers pick up logs starting with EVENT_JSON: (like consuming a message queue), e.g.
+    // EVENT_JSON:{"standard":"near_twitter","version":"1.0.0","event":"tweet_posted","data":[...]}
+    // Every payload carries the block height, block timestamp and the event's index within the
+    // block, so events can be totally ordered by (block_height, event_index)
+    fn emit_event(&mut self, event: TwitterEvent, tweet_id: TweetId, account_id: &AccountId) {
+        // The per-block sequence restarts at 0 in every new block
+        let block_height = env::block_height();
+        if block_height != self.last_event_block_height {
+            self.last_event_block_height = block_height;
+            self.next_event_index = 0;
+        }
+        let event_index = self.next_event_index;
+        self.next_event_index += 1;
+
+        let log = near_sdk::serde_json::json!({
+            "standard": EVENT_STANDARD,
+            "version": EVENT_STANDARD_VERSION,
+            "event": event.name(),
+            "data": [{
+                "tweet_id": tweet_id,
+                "account_id": account_id,
+                "block_height": block_height,
+                "block_timestamp": env::block_timestamp(),
+                "event_index": event_index,
+            }],
+        });
+        env::log_str(&format!("EVENT_JSON:{}", log));
+
+        self.notify_listeners(event, tweet_id, account_id);
+    }
+
+    // Fire a best-effort notification to every listener subscribed to this event
+    // Like delivering webhooks without retries: the calls run after this transaction commits,
+    // so a listener that fails (or runs out of gas) doesn't affect anything here
+    // Each listener gets a fixed LISTENER_GAS and no share of the leftover gas, so the cost of
+    // a write is predictable and a greedy listener can't take the caller's gas
+    fn notify_listeners(&self, event: TwitterEvent, tweet_id: TweetId, account_id: &AccountId) {
+        let args = near_sdk::serde_json::to_vec(&near_sdk::serde_json::json!({
+            "event": event,
+            "tweet_id": tweet_id,
+            "account_id": account_id,
+        }))
+        .expect("Event is serializable");
+
+        for (listener, event_mask) in self.listeners.iter() {
+            if event_mask & event.mask() != 0 {
+                Promise::new(listener.clone()).function_call_weight(
+                    "on_twitter_event".to_string(),
+                    args.clone(),
+                    NearToken::from_yoctonear(0),
+                    LISTENER_GAS,
+                    GasWeight(0),
+                );
+            }
+        }
+    }
+
+    // Append a user write to the operation log and return its op id
+    // The op id is also logged, so callers of methods that return a record can find it
+    // in the transaction's logs
+    fn record_op(
+        &mut self,
+        kind: OperationKind,
+        target: Option<OpTarget>,
+        idempotency_key: Option<String>,
+    ) -> u64 {
+        self.record_op_for(env::predecessor_account_id(), kind, target, idempotency_key)
+    }
+
+    // Append a write to the operation log on behalf of another account than the caller
+    // (e.g. a co-authored tweet is the proposer's post, even if a co-author's approval
+    // published it)
+    fn record_op_for(
+        &mut self,
+        account_id: AccountId,
+        kind: OperationKind,
+        target: Option<OpTarget>,
+        idempotency_key: Option<String>,
+    ) -> u64 {
+        let op_id = self.next_op_id;
+        self.next_op_id += 1;
+
+        if let Some(key) = &idempotency_key {
+            self.idempotency_keys
+                .insert((account_id.clone(), key.clone()), op_id);
+        }
+
+        self.operations.insert(
+            op_id,
+            Operation {
+                op_id,
+                kind,
+                account_id,
+                target,
+                timestamp: env::block_timestamp(),
+                idempotency_key,
+            },
+        );
+
+        // Retention: drop the entry that just fell out of the window (like a TTL index),
+        // together with its idempotency key - retries are only recognized for retained ops
+        if op_id >= OP_LOG_RETENTION {
+            if let Some(pruned) = self.operations.remove(&(op_id - OP_LOG_RETENTION)) {
+                if let Some(key) = pruned.idempotency_key {
+                    self.idempotency_keys.remove(&(pruned.account_id, key));
+                }
+            }
+        }
+
+        env::log_str(&format!("Operation #{}: {:?}", op_id, kind));
+        op_id
+    }
+
+    // Find the earlier write of the caller with this idempotency key, if the call is a retry
+    fn find_replay(
+        &self,
+        kind: OperationKind,
+        idempotency_key: &Option<String>,
+    ) -> Option<Operation> {
+        let key = idempotency_key.as_ref()?;
+        if key.is_empty() || key.len() > MAX_IDEMPOTENCY_KEY_LEN {
+            env::panic_str(&format!(
+                "Idempotency key must be between 1 and {} bytes",
+                MAX_IDEMPOTENCY_KEY_LEN
             ));
-            None
         }
+
+        let op_id = self
+            .idempotency_keys
+            .get(&(env::predecessor_account_id(), key.clone()))?;
+        let op = self.operations.get(op_id).cloned()?;
+        if op.kind != kind {
+            env::panic_str("Idempotency key was already used for a different operation");
+        }
+
+        env::log_str(&format!("Replayed operation #{}", op.op_id));
+        Some(op)
     }
 
-    // Delete a tweet - equivalent to DELETE /tweets/{id} endpoint
-    // Only the tweet author can delete their own tweets (authorization check)
-    pub fn delete_tweet(&mut self, tweet_id: u64) {
-        // Get who's trying to delete the tweet (like checking JWT/session)
-        let caller = env::predecessor_account_id();
+    // Original result of a replayed post: the tweet as it is now, with the original op id
+    fn replayed_tweet(&self, op: &Operation) -> WithOpId<Tweet> {
+        let tweet = match &op.target {
+            Some(OpTarget::Tweet(tweet_id)) => self.tweets.get(tweet_id).cloned(),
+            _ => None,
+        }
+        .unwrap_or_else(|| env::panic_str("The original tweet has been deleted"));
+        WithOpId {
+            op_id: op.op_id,
+            value: tweet,
+        }
+    }
 
-        // Check if tweet exists and verify ownership
-        // This is like: SELECT author FROM tweets WHERE id = ?
-        if let Some(tweet) = self.tweets.get(&tweet_id) {
-            // Authorization check - only author can delete their tweet
-            // Similar to checking if user owns the resource in REST API
-            if tweet.author == caller {
-                // Delete the tweet from storage
-                // Like: DELETE FROM tweets WHERE id = ?
-                self.tweets.remove(&tweet_id);
-                env::log_str(&format!("Tweet #{} deleted by @{}", tweet_id, caller));
+    // Shared v2 pagination: walk ids below the cursor (newest first), skipping deleted tweets
+    // and those rejected by `filter`, scanning at most MAX_PAGE_SCAN ids per page
+    fn tweet_page(
+        &self,
+        cursor: Option<TweetId>,
+        limit: Option<u64>,
+        filter: impl Fn(&Tweet) -> bool,
+    ) -> TweetPage {
+        let limit = limit.unwrap_or(10);
+        let mut tweets = Vec::new();
+        let mut next_id = cursor.map_or(self.next_tweet_id, |cursor| {
+            cursor.0.min(self.next_tweet_id)
+        });
+        let scan_end = next_id.saturating_sub(MAX_PAGE_SCAN);
+
+        while next_id > scan_end && (tweets.len() as u64) < limit {
+            next_id -= 1;
+            if let Some(tweet) = self.tweets.get(&TweetId(next_id)) {
+                if filter(tweet) {
+                    tweets.push(tweet.clone());
+                }
+            }
+        }
+
+        // Consistency mode (debug and test builds): a page is strictly newest first and never
+        // reaches past its cursor - chaining such pages can't skip or repeat a tweet, even when
+        // tweets are deleted in between, because the cursor is an id rather than a position
+        debug_assert!(tweets.windows(2).all(|pair| pair[0].id > pair[1].id));
+        debug_assert!(tweets.iter().all(|tweet| tweet.id.0 >= next_id));
+
+        TweetPage {
+            tweets,
+            // Everything below next_id is still unvisited
+            next_cursor: (next_id > 0).then_some(TweetId(next_id)),
+        }
+    }
+
+    // Binary search the time-ordered index for the first tweet id posted at or after
+    // `timestamp` (or strictly after it, if `exclusive`) - like an index range scan
+    fn first_tweet_id_at_or_after(&self, timestamp: Timestamp, exclusive: bool) -> u64 {
+        let (mut low, mut high) = (0, self.tweet_timestamps.len());
+        while low < high {
+            let middle = low + (high - low) / 2;
+            let posted_at = self.tweet_timestamps[middle];
+            if posted_at < timestamp || (exclusive && posted_at == timestamp) {
+                low = middle + 1;
             } else {
-                // Unauthorized deletion attempt - log security event
-                // In REST API, this would be 403 Forbidden
-                env::log_str(&format!(
-                    "User @{} attempted to delete tweet #{} but is not the author.",
-                    caller, tweet_id
-                ));
+                high = middle;
             }
-        } else {
-            // Tweet doesn't exist - log the attempt
-            // In REST API, this would be 404 Not Found
-            env::log_str(&format!(
-                "Attempt to delete non-existent tweet #{} by @{}",
-                tweet_id, caller
+        }
+        u64::from(low)
+    }
+
+    // Whether `delegate` currently holds posting rights for `author`
+    fn is_posting_delegate(&self, author: &AccountId, delegate: &AccountId) -> bool {
+        self.delegations.get(author).is_some_and(|delegations| {
+            delegations.iter().any(|delegation| {
+                delegation.delegate == *delegate && delegation.revoked_at.is_none()
+            })
+        })
+    }
+
+    // Add a tweet to the posting lists of its terms, keeping each list bounded
+    fn index_terms(&mut self, tweet_id: TweetId, terms: Vec<String>) {
+        let max_postings = self.config.max_postings_per_term as usize;
+        if max_postings == 0 {
+            return; // Search indexing is disabled
+        }
+
+        for term in terms {
+            // Tweet ids only grow, so pushing keeps every posting list sorted
+            let tweet_ids = self.search_index.entry(term).or_default();
+            tweet_ids.push(tweet_id);
+
+            // Keep only the newest postings (like a capped collection in MongoDB)
+            if tweet_ids.len() > max_postings {
+                let excess = tweet_ids.len() - max_postings;
+                tweet_ids.drain(..excess);
+            }
+        }
+    }
+
+    // An author's rollup of the current week, created on first use (an UPSERT, like below)
+    fn this_weeks_rollup(&mut self, author: &AccountId) -> &mut WeeklyRollup {
+        let week = current_week();
+        self.weekly_rollups
+            .entry((author.clone(), week))
+            .or_insert_with(|| WeeklyRollup {
+                week,
+                ..WeeklyRollup::default()
+            })
+    }
+
+    // A persona's rollup of the current week, created on first use like the author's
+    fn this_weeks_persona_rollup(&mut self, handle: &str) -> &mut WeeklyRollup {
+        let week = current_week();
+        self.persona_rollups
+            .entry((handle.to_string(), week))
+            .or_insert_with(|| WeeklyRollup {
+                week,
+                ..WeeklyRollup::default()
+            })
+    }
+
+    // Rollups for an inclusive week range, zero-filled - shared by the author and persona views
+    fn rollups_for_weeks<'a>(
+        &'a self,
+        from_week: u32,
+        to_week: u32,
+        get: impl Fn(u32) -> Option<&'a WeeklyRollup>,
+    ) -> Vec<WeeklyRollup> {
+        if to_week < from_week {
+            return Vec::new();
+        }
+        if to_week - from_week >= MAX_ROLLUP_WEEKS {
+            env::panic_str(&format!(
+                "Week range too large: at most {} weeks per call",
+                MAX_ROLLUP_WEEKS
             ));
         }
+
+        (from_week..=to_week)
+            .map(|week| {
+                get(week).cloned().unwrap_or(WeeklyRollup {
+                    week,
+                    ..WeeklyRollup::default()
+                })
+            })
+            .collect()
     }
 
-    // ============================================================================================
-    // READ METHODS (Free, don't modify state)
-    // ============================================================================================
-    // These are "view" methods - they don't cost gas and don't modify contract state
-    // Think of these as GET endpoints in your REST API
+    // All tweets liked within the last `window` blocks, best score first (see RisingTweet)
+    fn rank_rising(&self, window: u64, include_adult: Option<bool>) -> Vec<RisingTweet> {
+        // Sum the likes of every bucket that overlaps the window (SUM ... GROUP BY tweet_id)
+        let current = env::block_height() / RISING_BUCKET_BLOCKS;
+        let oldest = env::block_height().saturating_sub(window) / RISING_BUCKET_BLOCKS;
+        let mut totals: HashMap<TweetId, u64> = HashMap::new();
+        for bucket in oldest..=current {
+            let Some(stored) = self.like_buckets.get(&(bucket % RISING_BUCKETS)) else {
+                continue;
+            };
+            if stored.bucket != bucket {
+                continue;
+            }
+            for &(tweet_id, likes) in &stored.likes {
+                *totals.entry(tweet_id).or_default() += u64::from(likes);
+            }
+        }
 
-    // Get all tweets with pagination - like GET /tweets?offset=0&limit=10
-    // from_index: starting position (like OFFSET in SQL)
-    // limit: maximum number of tweets to return (like LIMIT in SQL)
-    pub fn get_all_tweets(&self, from_index: Option<u64>, limit: Option<u64>) -> Vec<Tweet> {
-        // Set default values if not provided (common REST API pattern)
-        let start = from_index.unwrap_or(0);
-        let limit_val = limit.unwrap_or(10);
+        // Deleted tweets are skipped here rather than removed from the buckets on delete
+        let now = env::block_timestamp();
+        let mut rising: Vec<RisingTweet> = totals
+            .into_iter()
+            .filter_map(|(tweet_id, recent_likes)| {
+                let tweet = self.tweets.get(&tweet_id)?;
+                if !is_listed(tweet, include_adult) {
+                    return None;
+                }
+                let age_hours = now.saturating_sub(tweet.timestamp) / (NANOS_PER_DAY / 24);
+                Some(RisingTweet {
+                    tweet: tweet.clone(),
+                    recent_likes,
+                    score: recent_likes * 1000 / (age_hours + 2),
+                })
+            })
+            .collect();
+        rising.sort_by(|a, b| {
+            b.score
+                .cmp(&a.score)
+                .then_with(|| b.tweet.id.cmp(&a.tweet.id))
+        });
+        rising
+    }
 
-        // Query tweets with pagination (like SELECT * FROM tweets LIMIT x OFFSET y)
-        self.tweets
-            .iter() // Iterate over all tweets
-            .skip(start as usize) // Skip 'start' number of tweets (OFFSET)
-            .take(limit_val as usize) // Take only 'limit_val' tweets (LIMIT)
-            .map(|(_key, tweet)| tweet.clone()) // Extract tweet objects (ignore keys)
-            .collect() // Collect into Vector to return
+    // Count a like in the current rising bucket, resetting the ring slot if it holds an old bucket
+    fn count_recent_like(&mut self, tweet_id: TweetId) {
+        let bucket = env::block_height() / RISING_BUCKET_BLOCKS;
+        let slot = self
+            .like_buckets
+            .entry(bucket % RISING_BUCKETS)
+            .or_insert_with(|| LikeBucket {
+                bucket,
+                likes: Vec::new(),
+            });
+        if slot.bucket != bucket {
+            *slot = LikeBucket {
+                bucket,
+                likes: Vec::new(),
+            };
+        }
+        match slot.likes.iter().position(|(id, _)| *id == tweet_id) {
+            Some(index) => slot.likes[index].1 += 1,
+            None if slot.likes.len() < MAX_TWEETS_PER_LIKE_BUCKET => slot.likes.push((tweet_id, 1)),
+            None => {}
+        }
     }
 
-    // Get specific tweet by ID - like GET /tweets/{id}
-    pub fn get_tweet_by_id(&self, tweet_id: u64) -> Option<Tweet> {
-        // Simple lookup by primary key
-        // Like: SELECT * FROM tweets WHERE id = ?
-        self.tweets.get(&tweet_id).cloned()
+    // Counters of the current day, created on first use (like an UPSERT into daily_stats)
+    fn todays_stats(&mut self) -> &mut DailyStats {
+        let day = (env::block_timestamp() / NANOS_PER_DAY) as u32;
+        self.daily_stats.entry(day).or_insert_with(|| DailyStats {
+            day,
+            ..DailyStats::default()
+        })
     }
 
-    // Get tweets by specific author with pagination - like GET /users/{id}/tweets
-    // This demonstrates filtering in blockchain storage (no SQL WHERE clause available)
-    pub fn get_tweets_by_author(
-        &self,
-        author_id: AccountId,
-        from_index: Option<u64>,
-        limit: Option<u64>,
-    ) -> Vec<Tweet> {
-        let start = from_index.unwrap_or(0);
-        let limit_val = limit.unwrap_or(10);
+    // Remember an active account and count it as new the first time we see it
+    fn track_account(&mut self, account_id: &AccountId) {
+        if self.known_accounts.insert(account_id.clone()) {
+            self.todays_stats().new_accounts += 1;
+        }
+    }
 
-        // We need to manually filter since blockchain storage doesn't have SQL-like queries
-        // This is like doing: SELECT * FROM tweets WHERE author = ? LIMIT x OFFSET y
-        // But we have to iterate through all tweets and filter manually
-        let mut author_tweets = Vec::new();
-        let mut count = 0;
-        let mut current_index = 0;
+    // Check topic tags on a new tweet: known ids only, no duplicates, at most a few
+    fn validate_topics(&self, mut topics: Vec<u32>) -> Vec<u32> {
+        topics.sort_unstable();
+        topics.dedup();
 
-        // Iterate through all tweets to find matches
-        for (_id, tweet) in self.tweets.iter() {
-            // Check if this tweet belongs to the requested author
-            if tweet.author == author_id {
-                // Apply pagination logic
-                if current_index >= start && count < limit_val {
-                    author_tweets.push(tweet.clone());
-                    count += 1;
+        if topics.len() > MAX_TOPICS_PER_TWEET {
+            env::panic_str(&format!(
+                "Too many topics: a tweet can have at most {} topics",
+                MAX_TOPICS_PER_TWEET
+            ));
+        }
+        if topics.iter().any(|topic_id| *topic_id >= self.topics.len()) {
+            env::panic_str("Unknown topic id");
+        }
+        topics
+    }
+
+    // Set or clear a tweet's adult label and record it in the operation log
+    fn apply_adult_flag(&mut self, tweet_id: TweetId, adult: bool) -> Tweet {
+        let tweet = self.tweets.get_mut(&tweet_id).expect("Tweet exists");
+        tweet.adult = adult;
+        let tweet = tweet.clone();
+
+        env::log_str(&format!(
+            "Tweet #{} adult label set to {} by @{}",
+            tweet_id,
+            adult,
+            env::predecessor_account_id()
+        ));
+        self.record_op(
+            OperationKind::SetAdultFlag,
+            Some(OpTarget::Tweet(tweet_id)),
+            None,
+        );
+        tweet
+    }
+
+    // Set or clear a legal hold and record it in the operation log
+    // Holds can be placed while a decommission is announced, but not once the final state
+    // is being digested
+    fn set_frozen(&mut self, tweet_id: TweetId, frozen: bool) -> Tweet {
+        if self.grace_period_over() {
+            env::panic_str("Decommission grace period is over: legal holds can't change");
+        }
+        let tweet = self
+            .tweets
+            .get_mut(&tweet_id)
+            .unwrap_or_else(|| env::panic_str("Tweet not found"));
+        tweet.frozen = frozen;
+        let tweet = tweet.clone();
+
+        let kind = if frozen {
+            OperationKind::FreezeTweet
+        } else {
+            OperationKind::UnfreezeTweet
+        };
+        env::log_str(&format!("Tweet #{} legal hold: {}", tweet_id, frozen));
+        self.record_op(kind, Some(OpTarget::Tweet(tweet_id)), None);
+        tweet
+    }
+
+    // Remove a tweet and everything stored for it - shared by delete_tweet and expire_tweets
+    // Freed storage goes back to the contract's balance (its storage staking shrinks)
+    fn remove_tweet(&mut self, tweet_id: TweetId) -> Tweet {
+        // After the decommission grace period the final digest is being taken
+        if self.grace_period_over() {
+            env::panic_str("Decommission grace period is over: tweets can't be deleted");
+        }
+        let deleted = self.tweets.remove(&tweet_id).expect("Tweet exists");
+
+        // Schedule removal of its search index entries (see gc_indexes)
+        // Off-chain tweets have no text left to tokenize, so their terms were kept aside
+        let terms = match deleted.storage_tier {
+            StorageTier::OffChain { .. } => {
+                self.offchain_terms.remove(&tweet_id).unwrap_or_default()
+            }
+            StorageTier::OnChain => tokenize(&deleted.text),
+        };
+        if !terms.is_empty() {
+            self.gc_queue.push((tweet_id, terms));
+        }
+        // An event tweet takes its details with it; its answers can't be reached any more
+        // (there is no event to list them), and are left in place rather than removed one by one
+        self.event_details.remove(&tweet_id);
+        deleted
+    }
+
+    // Count a call of a write method for get_method_metrics
+    // Only change methods call this, as their first statement: a view can't write state, so
+    // views are never counted. It costs every write one storage read and one write of the
+    // method's counter (about 0.2 Tgas, see tests/gas_benchmarks.rs)
+    fn count_call(&mut self, method: &str) {
+        *self.method_calls.entry(method.to_string()).or_insert(0) += 1;
+    }
+
+    // Take `cost` writes from the account's token bucket, refilling it for the time that passed
+    // first; panics when the bucket runs short (like answering 429 Too Many Requests)
+    // Batch writes pass the number of items, so a batch costs as much as posting them one by one
+    fn consume_rate_limit(&mut self, account_id: &AccountId, cost: u32) {
+        let Some(limit) = self.config.rate_limit.clone() else {
+            return;
+        };
+        if cost > limit.capacity {
+            env::panic_str("Batch is larger than the rate limit capacity");
+        }
+        let now = env::block_timestamp();
+
+        let mut bucket = match self.rate_buckets.get(account_id) {
+            Some(bucket) => *bucket,
+            None => {
+                self.rate_limited_accounts += 1;
+                RateBucket {
+                    tokens: limit.capacity,
+                    updated_at: now,
                 }
-                current_index += 1;
             }
+        };
 
-            // Optimization: stop early if we've found enough tweets
-            if count >= limit_val && current_index > start {
-                break;
+        // Refill whole tokens only and keep the remainder, so slow trickles still add up
+        let earned = now.saturating_sub(bucket.updated_at) / limit.refill_interval;
+        bucket.updated_at += earned * limit.refill_interval;
+        let tokens = (u64::from(bucket.tokens) + earned).min(u64::from(limit.capacity));
+        bucket.tokens = tokens as u32;
+        if bucket.tokens == limit.capacity {
+            // A full bucket doesn't bank time (like a capped leaky bucket)
+            bucket.updated_at = now;
+        }
+
+        if bucket.tokens < cost {
+            env::panic_str("Rate limit exceeded: try again later");
+        }
+        bucket.tokens -= cost;
+        self.rate_buckets.insert(account_id.clone(), bucket);
+        self.metered_writes += u64::from(cost);
+    }
+
+    // Guard for every write except deletions once a shutdown has been announced
+    fn assert_not_decommissioning(&self) {
+        if self.decommission_after.is_some() {
+            env::panic_str("Contract is being decommissioned: writes are frozen");
+        }
+    }
+
+    // Whether a decommission's grace period is over - from then on digest_final_state may be
+    // hashing the final state, so tweets must not change anymore
+    fn grace_period_over(&self) -> bool {
+        self.decommission_after
+            .is_some_and(|decommission_after| env::block_timestamp() >= decommission_after)
+    }
+
+    // Panic unless a decommission was announced and its grace period is over
+    fn assert_grace_period_over(&self) {
+        match self.decommission_after {
+            None => env::panic_str("Decommission has not been started"),
+            Some(decommission_after) if env::block_timestamp() < decommission_after => {
+                env::panic_str("Decommission grace period has not ended yet")
             }
+            Some(_) => {}
         }
+    }
 
-        author_tweets
+    // Shutdown progress, once the final digest is complete (the later steps need it)
+    fn digested_progress(&mut self) -> &mut DecommissionProgress {
+        self.assert_grace_period_over();
+        let next_tweet_id = self.next_tweet_id;
+        match self.decommission_progress.as_mut() {
+            Some(progress) if progress.next_digest_id == next_tweet_id => progress,
+            _ => env::panic_str("The final state digest is not complete yet"),
+        }
+    }
+
+    // Append a tweet to the accumulator, then merge equal-height subtrees like a binary
+    // counter carrying: leaf #5 completes nodes (1, 2) and nothing above, leaf #7 completes
+    // (1, 3), (2, 1) and (3, 0). Amortized, each tweet writes two nodes
+    fn append_tree_leaf(&mut self, tweet_id: TweetId, leaf_hash: [u8; 32]) {
+        let mut index = tweet_id.0;
+        let mut hash = leaf_hash;
+        let mut height = 0;
+        self.tweet_tree.insert((0, index), hash);
+        while index % 2 == 1 {
+            hash = merkle_node(&self.tree_node(height, index - 1), &hash);
+            height += 1;
+            index /= 2;
+            self.tweet_tree.insert((height, index), hash);
+        }
+    }
+
+    // Stored accumulator node (only nodes of complete subtrees are ever requested)
+    fn tree_node(&self, height: u8, index: u64) -> [u8; 32] {
+        *self
+            .tweet_tree
+            .get(&(height, index))
+            .expect("Accumulator node exists")
+    }
+
+    // Roots of the perfect subtrees covering the first `tree_size` leaves, left to right
+    fn tree_peaks(&self, tree_size: u64) -> Vec<[u8; 32]> {
+        let mut peaks = Vec::new();
+        let mut peak_start = 0;
+        for height in (0..64u8).rev() {
+            if tree_size & (1 << height) != 0 {
+                peaks.push(self.tree_node(height, peak_start >> height));
+                peak_start += 1 << height;
+            }
+        }
+        peaks
+    }
+
+    // Hash chain over the ids of the most recent live tweets (see Snapshot::recent_tweets_hash)
+    // Uses the same bounded newest-first scan as tweet_page, so the ids are exactly those
+    // of get_tweets_v2 with no cursor, limit SNAPSHOT_RECENT_TWEETS and include_adult
+    fn recent_tweets_hash(&self) -> (u32, [u8; 32]) {
+        let mut ids = self
+            .tweet_page(None, Some(SNAPSHOT_RECENT_TWEETS), |_| true)
+            .tweets
+            .into_iter()
+            .map(|tweet| tweet.id)
+            .collect::<Vec<_>>();
+        ids.reverse();
+
+        let mut digest = [0u8; 32];
+        for id in &ids {
+            let mut chunk = digest.to_vec();
+            chunk.extend(id.0.to_le_bytes());
+            digest = env::sha256_array(&chunk);
+        }
+        (ids.len() as u32, digest)
     }
 }
 
@@ -0,0 +1,199 @@
+// ================================================================================================
+// GAS AND STORAGE REGRESSION BENCHMARKS
+// ================================================================================================
+// Records the gas burned and the storage growth of the main operations at several state sizes
+// and fails when a budget below is exceeded - like a performance test in CI that fails when
+// p95 latency or memory per request regresses.
+//
+// The benchmarks run against a sandbox node and take a while, so they are ignored by default:
+//   cargo test --test gas_benchmarks -- --ignored --nocapture
+//
+// When a change makes an operation legitimately more expensive, raise its budget in the same PR
+// so reviewers can see (and discuss) the new cost.
+
+use near_workspaces::types::Gas;
+use near_workspaces::{Account, Contract};
+use serde_json::{json, Value};
+
+// Number of tweets already stored when each round of measurements is taken
+const STATE_SIZES: &[u64] = &[0, 25, 100];
+
+// Page size used for the feed read measurement
+const FEED_PAGE_SIZE: u64 = 50;
+
+// Gas every change method spends on count_call (the counters behind get_method_metrics):
+// one storage read and one write of the method's call counter. Views aren't counted, so
+// get_all_tweets doesn't pay it. The first call of a method also stores its counter (about
+// 100 bytes), which shows up in the storage delta of the first round (state size 0).
+const COUNT_CALL_GAS: Gas = Gas::from_ggas(200);
+
+// A write's budget: the gas of the operation itself plus COUNT_CALL_GAS
+const fn write_gas(operation_gas: Gas) -> Gas {
+    Gas::from_gas(operation_gas.as_gas() + COUNT_CALL_GAS.as_gas())
+}
+
+// Budget for one operation: maximum gas burned and maximum storage growth (in bytes)
+struct Budget {
+    operation: &'static str,
+    max_gas: Gas,
+    max_storage_delta: i64,
+}
+
+const BUDGETS: &[Budget] = &[
+    Budget {
+        operation: "post_tweet",
+        max_gas: write_gas(Gas::from_tgas(20)),
+        max_storage_delta: 2_000,
+    },
+    Budget {
+        operation: "like_tweet",
+        max_gas: write_gas(Gas::from_tgas(10)),
+        max_storage_delta: 500,
+    },
+    Budget {
+        operation: "delete_tweet",
+        max_gas: write_gas(Gas::from_tgas(10)),
+        max_storage_delta: 500,
+    },
+    Budget {
+        operation: "get_all_tweets",
+        max_gas: Gas::from_tgas(50),
+        max_storage_delta: 0,
+    },
+];
+
+// One measured operation
+struct Measurement {
+    operation: &'static str,
+    state_size: u64,
+    gas_burnt: Gas,
+    storage_delta: i64,
+}
+
+// Call `method` as a transaction and measure its gas and the contract's storage growth
+// View methods are called as transactions too, so that their gas is reported
+async fn measure(
+    contract: &Contract,
+    caller: &Account,
+    operation: &'static str,
+    args: Value,
+    state_size: u64,
+) -> Result<(Measurement, Value), Box<dyn std::error::Error>> {
+    let storage_before = contract.view_account().await?.storage_usage;
+    let outcome = caller
+        .call(contract.id(), operation)
+        .args_json(args)
+        .max_gas()
+        .transact()
+        .await?;
+    assert!(outcome.is_success(), "{:#?}", outcome.outcomes());
+    let storage_after = contract.view_account().await?.storage_usage;
+
+    let measurement = Measurement {
+        operation,
+        state_size,
+        gas_burnt: outcome.total_gas_burnt,
+        storage_delta: storage_after as i64 - storage_before as i64,
+    };
+    Ok((measurement, outcome.json()?))
+}
+
+/// Benchmark post/like/delete/feed reads at increasing state sizes
+/// Similar to load-testing an API with a growing database and checking it against an SLO
+#[tokio::test]
+#[ignore = "slow: run with `cargo test --test gas_benchmarks -- --ignored`"]
+async fn benchmark_gas_and_storage() -> Result<(), Box<dyn std::error::Error>> {
+    let contract_wasm = near_workspaces::compile_project("./").await?;
+    let sandbox = near_workspaces::sandbox().await?;
+    let contract = sandbox.dev_deploy(&contract_wasm).await?;
+    contract.call("new").transact().await?.into_result()?;
+    let author = sandbox.dev_create_account().await?;
+    let liker = sandbox.dev_create_account().await?;
+
+    let mut measurements = Vec::new();
+    let mut stored_tweets = 0;
+    for &state_size in STATE_SIZES {
+        // Arrange: Grow the state to the next size (like seeding a load-test database)
+        while stored_tweets < state_size {
+            author
+                .call(contract.id(), "post_tweet")
+                .args_json(json!({ "text": format!("Seed tweet #{} about #near", stored_tweets) }))
+                .transact()
+                .await?
+                .into_result()?;
+            stored_tweets += 1;
+        }
+
+        // Act: Measure each operation once at this size
+        let (post, tweet) = measure(
+            &contract,
+            &author,
+            "post_tweet",
+            json!({ "text": "Benchmarking gas and storage on NEAR #near #rust" }),
+            state_size,
+        )
+        .await?;
+        let tweet_id = tweet["id"].clone();
+        let (like, _) = measure(
+            &contract,
+            &liker,
+            "like_tweet",
+            json!({ "tweet_id": tweet_id }),
+            state_size,
+        )
+        .await?;
+        let (feed, _) = measure(
+            &contract,
+            &liker,
+            "get_all_tweets",
+            json!({ "from_index": 0, "limit": FEED_PAGE_SIZE }),
+            state_size,
+        )
+        .await?;
+        let (delete, _) = measure(
+            &contract,
+            &author,
+            "delete_tweet",
+            json!({ "tweet_id": tweet_id }),
+            state_size,
+        )
+        .await?;
+        measurements.extend([post, like, feed, delete]);
+    }
+
+    // Report every measurement (visible with --nocapture), then check them against the budgets
+    let mut regressions = Vec::new();
+    for measurement in &measurements {
+        println!(
+            "{:<16} state={:<5} gas={:>8.3} Tgas storage_delta={:>6} bytes",
+            measurement.operation,
+            measurement.state_size,
+            measurement.gas_burnt.as_gas() as f64 / 1e12,
+            measurement.storage_delta
+        );
+        let budget = BUDGETS
+            .iter()
+            .find(|budget| budget.operation == measurement.operation)
+            .expect("Every measured operation has a budget");
+        if measurement.gas_burnt > budget.max_gas
+            || measurement.storage_delta > budget.max_storage_delta
+        {
+            regressions.push(format!(
+                "{} at {} tweets: {} gas, {} bytes (budget {} gas, {} bytes)",
+                measurement.operation,
+                measurement.state_size,
+                measurement.gas_burnt.as_gas(),
+                measurement.storage_delta,
+                budget.max_gas.as_gas(),
+                budget.max_storage_delta
+            ));
+        }
+    }
+    assert!(
+        regressions.is_empty(),
+        "Budget exceeded:\n{}",
+        regressions.join("\n")
+    );
+
+    Ok(())
+}
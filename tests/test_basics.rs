@@ -24,11 +24,20 @@
 
 // Import NEAR testing utilities and our contract
 use near_sdk::{
-    test_utils::{accounts, VMContextBuilder}, // Utilities for creating test accounts and context
+    json_types::Base64VecU8,
+    near,
+    store::IterableMap, // Used to recreate the state layout of the first deployed version
+    test_utils::{accounts, get_logs, VMContextBuilder}, // Utilities for test accounts, context and logs
     testing_env,
-    AccountId, // Environment setup and account types
+    AccountId,     // Environment setup and account types
+    PromiseResult, // Outcome of a cross-contract call, fed to callbacks in tests
 };
-use near_twitter_example_rs::TwitterContract; // Our smart contract to test
+use near_twitter_example_rs::{
+    AccountSettings, AdminProposalStatus, ArchiveRecord, Config, ContractMode, FederatedPost,
+    IdentityPlatform, License, OpTarget, OperationKind, RankingConfig, RateLimitConfig,
+    RateLimitMetrics, RsvpStatus, SettingsPatch, StorageTier, Tweet, TweetId, TweetPage,
+    TwitterContract, TwitterEvent,
+}; // Our smart contract to test
 
 // ================================================================================================
 // TEST MODULE
@@ -66,10 +75,134 @@ mod tests {
         builder
     }
 
+    /// Parse the NEP-297 events logged since the context was last set up
+    fn event_logs() -> Vec<near_sdk::serde_json::Value> {
+        get_logs()
+            .iter()
+            .filter_map(|log| log.strip_prefix("EVENT_JSON:"))
+            .map(|json| near_sdk::serde_json::from_str(json).unwrap())
+            .collect()
+    }
+
+    /// Verify an inclusion proof the way a third party would, using only the proof itself
+    fn verify_inclusion(proof: &near_twitter_example_rs::InclusionProof) -> bool {
+        let mut hash = proof.leaf_hash.0.clone();
+        for step in &proof.path {
+            let (left, right) = if step.sibling_is_left {
+                (&step.sibling.0, &hash)
+            } else {
+                (&hash, &step.sibling.0)
+            };
+            let mut data = vec![1u8];
+            data.extend(left);
+            data.extend(right);
+            hash = near_sdk::env::sha256(&data);
+        }
+        let peaks: Vec<u8> = proof.peaks.iter().flat_map(|peak| peak.0.clone()).collect();
+        proof.peaks[proof.peak_index as usize].0 == hash
+            && near_sdk::env::sha256(&peaks) == proof.root.0
+    }
+
+    /// Tweet layout of the first deployed version (state schema version 1)
+    #[near(serializers = [borsh])]
+    struct BaselineTweet {
+        id: u64,
+        author: AccountId,
+        text: String,
+        timestamp: u64,
+        likes: u64,
+    }
+
+    /// State layout of the first deployed version, as the production account still stores it
+    #[near(serializers = [borsh])]
+    struct BaselineContract {
+        tweets: IterableMap<u64, BaselineTweet>,
+        next_tweet_id: u64,
+    }
+
     // ============================================================================================
     // INITIALIZATION TESTS
     // ============================================================================================
 
+    /// Test upgrading state written by the first deployed version
+    /// Similar to running a schema migration plus a batched backfill against a production dump
+    #[test]
+    fn test_migrate_from_baseline_state() {
+        // Setup: Baseline state with tweets 0 and 2 (tweet 1 was deleted before the upgrade)
+        let mut context = get_context(accounts(0));
+        context.block_timestamp(5_000);
+        testing_env!(context.build());
+        let mut baseline = BaselineContract {
+            tweets: IterableMap::new(b"t"),
+            next_tweet_id: 3,
+        };
+        for (id, text, likes) in [(0, "Hello NEAR", 2), (2, "Still here", 0)] {
+            let tweet = BaselineTweet {
+                id,
+                author: accounts(1),
+                text: text.to_string(),
+                timestamp: 1_000 + id,
+                likes,
+            };
+            baseline.tweets.insert(id, tweet);
+        }
+        baseline.tweets.flush();
+        near_sdk::env::state_write(&baseline);
+        drop(baseline);
+
+        // Act: The new code is deployed and migrate() is called
+        let mut contract = TwitterContract::migrate();
+
+        // Assert: The contract stays read-only until every legacy tweet is converted
+        let status = contract.get_status();
+        assert_eq!(status.mode, ContractMode::ReadOnly);
+        assert_eq!(status.migration_pending, 3);
+        assert_eq!(contract.get_tweet_by_id(TweetId(0)), None);
+
+        // Act: Convert the tweets in two batches
+        assert_eq!(contract.migrate_legacy_tweets(2), 2);
+        assert_eq!(contract.get_status().migration_pending, 1);
+        assert_eq!(contract.migrate_legacy_tweets(10), 1);
+
+        // Assert: Tweets keep their ids and data, and the contract is active again
+        let status = contract.get_status();
+        assert_eq!(status.mode, ContractMode::Active);
+        assert_eq!(status.migration_pending, 0);
+        let first = contract.get_tweet_by_id(TweetId(0)).unwrap();
+        assert_eq!(first.author, accounts(1));
+        assert_eq!(first.text, "Hello NEAR");
+        assert_eq!(first.timestamp, 1_000);
+        assert_eq!(first.likes, 2);
+        assert!(contract.verify_tweet_content(TweetId(0), "Hello NEAR".to_string()));
+        assert_eq!(contract.get_tweet_by_id(TweetId(1)), None);
+
+        // Assert: Converted tweets are indexed like new posts
+        let found = contract.search_tweets("still".to_string(), None, None, None);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, TweetId(2));
+        let after: Vec<TweetId> = contract
+            .get_tweets_after(1_000, None, None)
+            .iter()
+            .map(|tweet| tweet.id)
+            .collect();
+        assert_eq!(after, vec![TweetId(2)]);
+        let proof = contract.get_inclusion_proof(TweetId(2), None).unwrap();
+        assert!(verify_inclusion(&proof));
+        assert!(contract.check_invariants(10, None).violations.is_empty());
+
+        // Assert: New tweets continue after the legacy ids
+        context.predecessor_account_id(accounts(2));
+        testing_env!(context.build());
+        let tweet = contract.post_tweet(
+            "First post after the upgrade".to_string(),
+            None,
+            None,
+            None,
+            None,
+        );
+        assert_eq!(tweet.id, TweetId(3));
+    }
+
     /// Test contract initialization (constructor)
     /// Similar to testing that your service starts up correctly
     #[test]
@@ -101,17 +234,170 @@ mod tests {
         let mut contract = TwitterContract::new();
 
         // Act: Post a tweet (like making a POST request)
-        let tweet = contract.post_tweet("Hello NEAR!".to_string());
+        let tweet = contract.post_tweet("Hello NEAR!".to_string(), None, None, None, None);
 
         // Assert: Verify the tweet was created correctly
         // Check all the fields like you would verify a REST API response
-        assert_eq!(tweet.id, 0); // First tweet should have ID 0
+        assert_eq!(tweet.id, TweetId(0)); // First tweet should have ID 0
         assert_eq!(tweet.author, accounts(1)); // Author should be the caller
         assert_eq!(tweet.text, "Hello NEAR!"); // Content should match input
         assert_eq!(tweet.likes, 0); // New tweets start with 0 likes
                                     // Note: We can't check timestamp easily in tests, but it's set by the contract
     }
 
+    /// Test posting a tweet with a re-use license
+    /// Similar to testing an optional field in the POST /tweets request body
+    #[test]
+    fn test_post_tweet_with_license() {
+        let context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+
+        // Act: Post one licensed and one unlicensed tweet
+        let licensed = contract.post_tweet(
+            "Free to share".to_string(),
+            Some(License::CcBy),
+            None,
+            None,
+            None,
+        );
+        let unlicensed =
+            contract.post_tweet("No license given".to_string(), None, None, None, None);
+
+        // Assert: The license is stored and returned by views
+        assert_eq!(licensed.license, Some(License::CcBy));
+        assert_eq!(unlicensed.license, None);
+        assert_eq!(
+            contract.get_tweet_by_id(TweetId(0)).unwrap().license,
+            Some(License::CcBy)
+        );
+    }
+
+    /// Test hybrid on/off-chain storage of long tweets
+    /// Similar to testing an upload endpoint that keeps large payloads in blob storage
+    #[test]
+    fn test_post_tweet_off_chain() {
+        // Setup: The owner limits on-chain text to 10 bytes
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.update_config(Config {
+            max_onchain_bytes: Some(10),
+            ..Config::default()
+        });
+        assert_eq!(contract.get_config().max_onchain_bytes, Some(10));
+
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+
+        // Act: Post a short and a long tweet
+        let short = contract.post_tweet("Short".to_string(), None, None, None, None);
+        let long_text = "This tweet is too long for on-chain storage".to_string();
+        let long = contract.post_tweet(
+            long_text.clone(),
+            None,
+            Some("ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi".to_string()),
+            None,
+            None,
+        );
+
+        // Assert: Short text stays on-chain, long text is replaced by a pointer + hash
+        assert_eq!(short.storage_tier, StorageTier::OnChain);
+        assert_eq!(short.text, "Short");
+        assert!(matches!(long.storage_tier, StorageTier::OffChain { .. }));
+        assert_eq!(long.text, "");
+        assert!(contract.verify_tweet_content(long.id, long_text));
+    }
+
+    /// Test that long tweets need a content pointer once hybrid storage is enabled
+    #[test]
+    #[should_panic(expected = "content_uri is required")]
+    fn test_post_long_tweet_without_content_uri() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.update_config(Config {
+            max_onchain_bytes: Some(10),
+            ..Config::default()
+        });
+
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        contract.post_tweet("This tweet is too long".to_string(), None, None, None, None);
+    }
+
+    /// Test tagging tweets with curated topics and reading a topic feed
+    /// Similar to testing POST /tweets with a category and GET /topics/{id}/tweets
+    #[test]
+    fn test_topics() {
+        // Setup: The owner creates two topics
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        let rust = contract.create_topic("Rust".to_string(), "All things Rust".to_string());
+        let defi = contract.create_topic("DeFi".to_string(), "Decentralized finance".to_string());
+        assert_eq!(
+            contract.get_topics(None, None),
+            vec![rust.clone(), defi.clone()]
+        );
+
+        // Act: A user posts tweets with and without topics
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        contract.post_tweet(
+            "Borrow checker".to_string(),
+            None,
+            None,
+            Some(vec![rust.id]),
+            None,
+        );
+        contract.post_tweet("Untagged".to_string(), None, None, None, None);
+        let both = contract.post_tweet(
+            "Rust for DeFi".to_string(),
+            None,
+            None,
+            Some(vec![defi.id, rust.id, rust.id]), // Duplicates are ignored
+            None,
+        );
+        assert_eq!(both.topics, vec![rust.id, defi.id]);
+
+        // Assert: Topic feeds only contain tagged tweets
+        let rust_feed = contract.get_topic_feed(rust.id, None, None, None);
+        assert_eq!(rust_feed.len(), 2);
+        assert_eq!(
+            contract.get_topic_feed(defi.id, None, None, None)[0].text,
+            "Rust for DeFi"
+        );
+    }
+
+    /// Test that tweets can't be tagged with unknown topics (like a foreign key violation)
+    #[test]
+    #[should_panic(expected = "Unknown topic id")]
+    fn test_post_tweet_with_unknown_topic() {
+        let context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+
+        contract.post_tweet(
+            "Mystery topic".to_string(),
+            None,
+            None,
+            Some(vec![42]),
+            None,
+        );
+    }
+
+    /// Test that only the owner can curate topics
+    #[test]
+    #[should_panic(expected = "Only the contract owner can call this method")]
+    fn test_create_topic_requires_owner() {
+        let context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+
+        contract.create_topic("Spam".to_string(), "Not curated".to_string());
+    }
+
     /// Test liking tweets
     /// Similar to testing POST /tweets/{id}/like endpoint
     #[test]
@@ -120,87 +406,2991 @@ mod tests {
         let context = get_context(accounts(1));
         testing_env!(context.build());
         let mut contract = TwitterContract::new();
-        contract.post_tweet("Likeable tweet".to_string());
+        contract.post_tweet("Likeable tweet".to_string(), None, None, None, None);
 
         // Act & Assert: Like the tweet
-        let liked_tweet = contract.like_tweet(0);
+        let liked_tweet = contract.like_tweet(TweetId(0), None);
         assert!(liked_tweet.is_some()); // Should return the tweet
         assert_eq!(liked_tweet.unwrap().likes, 1); // Should have 1 like
 
         // Act & Assert: Like the same tweet again (multiple likes allowed)
-        let liked_again = contract.like_tweet(0);
+        let liked_again = contract.like_tweet(TweetId(0), None);
         assert!(liked_again.is_some());
         assert_eq!(liked_again.unwrap().likes, 2); // Should have 2 likes
 
         // Act & Assert: Try to like non-existent tweet (error case)
-        let non_existent = contract.like_tweet(999);
+        let non_existent = contract.like_tweet(TweetId(999), None);
         assert!(non_existent.is_none()); // Should return None (like 404)
     }
 
-    /// Test tweet deletion with authorization
-    /// Similar to testing DELETE /tweets/{id} with ownership verification
+    /// Test co-authored tweets: published only after every co-author approves
+    /// Similar to testing a draft -> review -> publish workflow
     #[test]
-    fn test_delete_tweet() {
-        // Setup: Create a tweet from user 1
+    fn test_coauthored_tweet() {
+        // Setup: User 1 proposes a tweet with users 2 and 3
         let mut context = get_context(accounts(1));
         testing_env!(context.build());
         let mut contract = TwitterContract::new();
-        contract.post_tweet("Tweet to delete".to_string());
+        let draft = contract.propose_coauthored_tweet(
+            "Written together".to_string(),
+            vec![accounts(3), accounts(2), accounts(1)],
+        );
 
-        // Verify tweet exists
-        assert!(contract.get_tweet_by_id(0).is_some());
+        // Assert: The proposer is not listed as a co-author, duplicates are normalized
+        assert_eq!(draft.coauthors, vec![accounts(2), accounts(3)]);
+        assert!(contract.get_all_tweets(None, None, None).is_empty());
 
-        // Act: Delete the tweet as the author (should succeed)
-        contract.delete_tweet(0);
+        // Act: First approval keeps the draft pending
+        context.predecessor_account_id(accounts(2));
+        testing_env!(context.build());
+        assert!(contract.approve_coauthorship(draft.id).is_none());
+        assert_eq!(
+            contract.get_draft(draft.id).unwrap().approvals,
+            vec![accounts(2)]
+        );
 
-        // Assert: Tweet should be deleted
-        assert!(contract.get_tweet_by_id(0).is_none());
+        // Act: Last approval publishes the tweet
+        context.predecessor_account_id(accounts(3));
+        testing_env!(context.build());
+        let approval = contract.approve_coauthorship(draft.id);
+        let tweet = approval.value.unwrap();
 
-        // Edge Case: Try to delete non-existent tweet (should not panic)
-        contract.delete_tweet(999); // Should handle gracefully
+        // Assert: The tweet lists all authors and the draft is gone
+        assert_eq!(tweet.author, accounts(1));
+        assert_eq!(tweet.coauthors, vec![accounts(2), accounts(3)]);
+        assert_eq!(contract.get_tweet_by_id(tweet.id), Some(tweet));
+        assert!(contract.get_draft(draft.id).is_none());
 
-        // Authorization Test: Create another tweet and try to delete as different user
-        contract.post_tweet("Another tweet".to_string());
+        // Assert: The approval is the co-author's op, the published tweet the proposer's
+        assert_eq!(
+            contract.get_op(approval.op_id).unwrap().account_id,
+            accounts(3)
+        );
+        let post = contract.get_op(approval.op_id + 1).unwrap();
+        assert_eq!(post.kind, OperationKind::PostTweet);
+        assert_eq!(post.account_id, accounts(1));
+    }
 
-        // Switch to different user context (like switching JWT token)
+    /// Test that only listed co-authors can approve a draft
+    #[test]
+    #[should_panic(expected = "Only a listed co-author can approve this draft")]
+    fn test_approve_coauthorship_requires_coauthor() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        let draft = contract.propose_coauthored_tweet("Ours".to_string(), vec![accounts(2)]);
+
+        // Act: An unrelated account tries to approve
+        context.predecessor_account_id(accounts(3));
+        testing_env!(context.build());
+        contract.approve_coauthorship(draft.id);
+    }
+
+    /// Test rejecting and cancelling drafts
+    /// Similar to testing DELETE /drafts/{id} and a reviewer's "request changes"
+    #[test]
+    fn test_reject_and_cancel_draft() {
+        // Setup: User 1 proposes two drafts with user 2
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        let rejected = contract.propose_coauthored_tweet("Ours".to_string(), vec![accounts(2)]);
+        let cancelled =
+            contract.propose_coauthored_tweet("Also ours".to_string(), vec![accounts(2)]);
+
+        // Act: The co-author rejects the first draft, the proposer cancels the second
         context.predecessor_account_id(accounts(2));
         testing_env!(context.build());
+        let reject_op = contract.reject_coauthorship(rejected.id);
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        let cancel_op = contract.cancel_draft(cancelled.id);
 
-        // Act: Try to delete as different user (should fail)
-        contract.delete_tweet(1);
+        // Assert: Both drafts are gone and the writes are in the operation log
+        assert!(contract.get_draft(rejected.id).is_none());
+        assert!(contract.get_draft(cancelled.id).is_none());
+        assert_eq!(
+            contract.get_op(reject_op).unwrap().kind,
+            OperationKind::RejectDraft
+        );
+        assert_eq!(
+            contract.get_op(cancel_op).unwrap().kind,
+            OperationKind::CancelDraft
+        );
+        assert!(contract.check_invariants(10, None).violations.is_empty());
+    }
 
-        // Switch back to original author to verify tweet still exists
+    /// Test that anyone can clean up an expired draft
+    #[test]
+    fn test_cancel_expired_draft() {
+        const DAY: u64 = 24 * 60 * 60 * 1_000_000_000;
+
+        // Setup: User 1 proposes a draft with user 2
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        let draft = contract.propose_coauthored_tweet("Ours".to_string(), vec![accounts(2)]);
+        assert_eq!(draft.expires_at, draft.created_at + 7 * DAY);
+
+        // Act: An unrelated account cancels the draft after it expired
+        context
+            .predecessor_account_id(accounts(3))
+            .block_timestamp(draft.expires_at);
+        testing_env!(context.build());
+        contract.cancel_draft(draft.id);
+
+        // Assert: The draft is gone
+        assert!(contract.get_draft(draft.id).is_none());
+    }
+
+    /// Test that only the proposer can cancel a draft that hasn't expired yet
+    #[test]
+    #[should_panic(expected = "Only the proposer can cancel a draft before it expires")]
+    fn test_cancel_draft_requires_proposer() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        let draft = contract.propose_coauthored_tweet("Ours".to_string(), vec![accounts(2)]);
+
+        // Act: A co-author tries to cancel instead of rejecting
+        context.predecessor_account_id(accounts(2));
+        testing_env!(context.build());
+        contract.cancel_draft(draft.id);
+    }
+
+    /// Test that an expired draft can't be approved
+    #[test]
+    #[should_panic(expected = "Draft has expired")]
+    fn test_approve_expired_draft() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        let draft = contract.propose_coauthored_tweet("Ours".to_string(), vec![accounts(2)]);
+
+        // Act: The co-author approves too late
+        context
+            .predecessor_account_id(accounts(2))
+            .block_timestamp(draft.expires_at);
+        testing_env!(context.build());
+        contract.approve_coauthorship(draft.id);
+    }
+
+    /// Test that a draft that outgrew a lowered max_onchain_bytes is discarded instead of stuck
+    #[test]
+    fn test_approve_discards_draft_over_size_limit() {
+        // Setup: User 1 proposes a draft, then the owner lowers the on-chain limit
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        let draft =
+            contract.propose_coauthored_tweet("Written together".to_string(), vec![accounts(2)]);
+        context.predecessor_account_id(accounts(0));
+        testing_env!(context.build());
+        contract.update_config(Config {
+            max_onchain_bytes: Some(10),
+            ..Config::default()
+        });
+
+        // Act: The co-author approves
+        context.predecessor_account_id(accounts(2));
+        testing_env!(context.build());
+        let approval = contract.approve_coauthorship(draft.id);
+
+        // Assert: Nothing is published and the draft is discarded
+        assert!(approval.is_none());
+        assert_eq!(
+            contract.get_op(approval.op_id).unwrap().kind,
+            OperationKind::DiscardDraft
+        );
+        assert!(contract.get_draft(draft.id).is_none());
+        assert!(contract.get_all_tweets(None, None, None).is_empty());
+    }
+
+    /// Test posting through a delegate (ghost-writer) and revoking the delegation
+    /// Similar to testing team member access to a brand account
+    #[test]
+    fn test_posting_delegate() {
+        // Setup: User 1 lets user 2 post on their behalf
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.grant_posting_delegate(accounts(2));
+
+        // Act: User 2 posts with user 1 as the byline
+        context.predecessor_account_id(accounts(2));
+        testing_env!(context.build());
+        let tweet = contract.post_tweet_on_behalf(
+            accounts(1),
+            "Ghost-written".to_string(),
+            None,
+            None,
+            None,
+            None,
+        );
+
+        // Assert: Both the byline account and the actual signer are recorded
+        assert_eq!(tweet.author, accounts(1));
+        assert_eq!(tweet.signer, Some(accounts(2)));
+
+        // Act: User 1 revokes the delegation
         context.predecessor_account_id(accounts(1));
         testing_env!(context.build());
+        contract.revoke_posting_delegate(accounts(2));
 
-        // Assert: Tweet should still exist (deletion should have failed)
-        assert!(contract.get_tweet_by_id(1).is_some());
+        // Assert: The delegation stays on record as revoked
+        let delegations = contract.get_posting_delegates(accounts(1));
+        assert_eq!(delegations.len(), 1);
+        assert!(delegations[0].revoked_at.is_some());
     }
 
-    // ============================================================================================
-    // READ OPERATION TESTS (Methods that don't modify state)
-    // ============================================================================================
+    /// Test that posting on someone's behalf requires an active delegation
+    #[test]
+    #[should_panic(expected = "Caller is not a posting delegate of this author")]
+    fn test_post_tweet_on_behalf_without_delegation() {
+        let context = get_context(accounts(2));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
 
-    /// Test retrieving specific tweet by ID
-    /// Similar to testing GET /tweets/{id} endpoint
+        contract.post_tweet_on_behalf(accounts(1), "Impostor".to_string(), None, None, None, None);
+    }
+
+    /// Test brand personas: creation, managers and attributed posts
+    /// Similar to testing an organization account whose members post on its behalf
     #[test]
-    fn test_get_tweet_by_id() {
-        // Setup: Create a contract and post a tweet
+    fn test_personas() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.create_persona("acme".to_string());
+        let persona = contract.add_persona_manager("acme".to_string(), accounts(2));
+        assert_eq!(persona.owner, accounts(1));
+        assert_eq!(persona.managers[0].delegate, accounts(2));
+
+        // Act: The owner and a manager both post as the persona
+        let by_owner = contract.post_as_persona(
+            "acme".to_string(),
+            "Launch day!".to_string(),
+            None,
+            None,
+            None,
+            None,
+        );
+        context.predecessor_account_id(accounts(2));
+        testing_env!(context.build());
+        let by_manager = contract.post_as_persona(
+            "acme".to_string(),
+            "Support hours are 9-5".to_string(),
+            None,
+            None,
+            None,
+            None,
+        );
+
+        // Assert: Both are attributed to the persona, its owner and who actually posted
+        assert_eq!(by_owner.persona.as_deref(), Some("acme"));
+        assert_eq!(by_owner.author, accounts(1));
+        assert_eq!(by_owner.signer, None);
+        assert_eq!(by_manager.persona.as_deref(), Some("acme"));
+        assert_eq!(by_manager.author, accounts(1));
+        assert_eq!(by_manager.signer, Some(accounts(2)));
+        assert_eq!(
+            contract.get_tweet_by_id(by_manager.id),
+            Some(by_manager.value)
+        );
+
+        // Act: The owner removes the manager
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        contract.remove_persona_manager("acme".to_string(), accounts(2));
+
+        // Assert: The grant stays on record as revoked
+        let persona = contract.get_persona("acme".to_string()).unwrap();
+        assert!(persona.managers[0].revoked_at.is_some());
+        assert!(contract.get_persona("nobody".to_string()).is_none());
+    }
+
+    /// Test that a removed manager can no longer post as the persona
+    /// Similar to testing that a former organization member gets 403
+    #[test]
+    #[should_panic(expected = "Caller can't post as this persona")]
+    fn test_post_as_persona_after_removal() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.create_persona("acme".to_string());
+        contract.add_persona_manager("acme".to_string(), accounts(2));
+        contract.remove_persona_manager("acme".to_string(), accounts(2));
+
+        // Act
+        context.predecessor_account_id(accounts(2));
+        testing_env!(context.build());
+        contract.post_as_persona(
+            "acme".to_string(),
+            "Still here?".to_string(),
+            None,
+            None,
+            None,
+            None,
+        );
+    }
+
+    /// Test account settings and the default license they provide
+    /// Similar to testing PATCH /users/me/settings and its effect on new posts
+    #[test]
+    fn test_account_settings() {
         let context = get_context(accounts(1));
         testing_env!(context.build());
         let mut contract = TwitterContract::new();
-        let posted_tweet = contract.post_tweet("Test tweet".to_string());
+        assert_eq!(
+            contract.get_settings(accounts(1)),
+            AccountSettings::default()
+        );
 
-        // Act: Retrieve the tweet by ID
-        let retrieved_tweet = contract.get_tweet_by_id(0);
+        // Act: Set a default license and a language
+        contract.update_settings(SettingsPatch {
+            default_license: Some(License::CcBy),
+            language: Some("pt-BR".to_string()),
+            ..Default::default()
+        });
+        // A later patch only changes the fields it contains
+        let settings = contract.update_settings(SettingsPatch {
+            show_sensitive_content: Some(true),
+            ..Default::default()
+        });
 
-        // Assert: Should return the correct tweet
-        assert!(retrieved_tweet.is_some());
-        assert_eq!(retrieved_tweet.unwrap(), posted_tweet);
+        // Assert
+        assert_eq!(settings.default_license, Some(License::CcBy));
+        assert_eq!(settings.language.as_deref(), Some("pt-BR"));
+        assert!(settings.show_sensitive_content);
+        assert_eq!(contract.get_settings(accounts(1)), settings);
 
-        // Edge Case: Try to get non-existent tweet
-        let non_existent = contract.get_tweet_by_id(999);
-        assert!(non_existent.is_none()); // Should return None (like 404)
+        // Assert: The default applies only when no license is given explicitly
+        let tweet = contract.post_tweet("Defaults".to_string(), None, None, None, None);
+        assert_eq!(tweet.license, Some(License::CcBy));
+        let tweet =
+            contract.post_tweet("Explicit".to_string(), Some(License::Cc0), None, None, None);
+        assert_eq!(tweet.license, Some(License::Cc0));
+
+        // Act: Clear the default license again
+        let settings = contract.update_settings(SettingsPatch {
+            clear_default_license: true,
+            ..Default::default()
+        });
+
+        // Assert: Other settings are kept and new tweets get no license
+        assert_eq!(settings.default_license, None);
+        assert_eq!(settings.language.as_deref(), Some("pt-BR"));
+        let tweet = contract.post_tweet("No license".to_string(), None, None, None, None);
+        assert_eq!(tweet.license, None);
+    }
+
+    /// Test that malformed language tags are rejected
+    #[test]
+    #[should_panic(expected = "Invalid language tag")]
+    fn test_update_settings_with_invalid_language() {
+        let context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+
+        contract.update_settings(SettingsPatch {
+            language: Some("en_US".to_string()),
+            ..Default::default()
+        });
+    }
+
+    /// Test registering, resolving and clicking short links
+    /// Similar to testing a URL shortener's create/redirect endpoints
+    #[test]
+    fn test_short_links() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+
+        // Act
+        let code = contract.register_short_link("https://near.org/blog".to_string());
+        let other = contract.register_short_link("https://example.com".to_string());
+
+        // Assert: Codes are unique and resolve to the destination and its owner
+        assert_ne!(code, other);
+        let link = contract.resolve_link(code.clone()).unwrap();
+        assert_eq!(link.url, "https://near.org/blog");
+        assert_eq!(link.owner, accounts(1));
+        assert_eq!(contract.resolve_link("unknown".to_string()), None);
+
+        // Act & Assert: Anyone can report clicks
+        context.predecessor_account_id(accounts(2));
+        testing_env!(context.build());
+        assert_eq!(contract.record_click(code.clone()), 1);
+        assert_eq!(contract.record_click(code.clone()), 2);
+        assert_eq!(contract.resolve_link(code).unwrap().clicks, 2);
+    }
+
+    /// Test that only web URLs can be shortened
+    #[test]
+    #[should_panic(expected = "Short link url must start with https:// or http://")]
+    fn test_register_short_link_with_invalid_url() {
+        let context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+
+        contract.register_short_link("javascript:alert(1)".to_string());
+    }
+
+    /// Test event tweets and RSVPs
+    /// Similar to testing a meetup API: create an event, answer, list attendees
+    #[test]
+    fn test_event_rsvps() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+
+        // Act: User 1 announces a meetup
+        let event = contract.post_event(
+            "Rust meetup".to_string(),
+            0,
+            "Lisbon".to_string(),
+            "Bring a laptop".to_string(),
+        );
+
+        // Assert: The event is also a regular tweet with a readable summary
+        let tweet = contract.get_tweet_by_id(event.tweet_id).unwrap();
+        assert!(tweet.text.contains("Rust meetup"));
+        assert!(tweet.text.contains("Lisbon"));
+
+        // Act: Users 2 and 3 answer; user 2 changes their mind
+        context.predecessor_account_id(accounts(2));
+        testing_env!(context.build());
+        contract.rsvp(event.tweet_id, RsvpStatus::Interested);
+        contract.rsvp(event.tweet_id, RsvpStatus::Going);
+        context.predecessor_account_id(accounts(3));
+        testing_env!(context.build());
+        contract.rsvp(event.tweet_id, RsvpStatus::NotGoing);
+
+        // Assert: One answer per account, filterable by status
+        assert_eq!(contract.get_event(event.tweet_id).unwrap().rsvp_count, 2);
+        let going =
+            contract.get_event_attendees(event.tweet_id, Some(RsvpStatus::Going), None, None);
+        assert_eq!(going.len(), 1);
+        assert_eq!(going[0].account_id, accounts(2));
+
+        // Act & Assert: Deleting the tweet removes the event
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        contract.delete_tweet(event.tweet_id);
+        assert_eq!(contract.get_event(event.tweet_id), None);
+    }
+
+    /// Test that event announcements must fit on-chain when hybrid storage is enabled
+    #[test]
+    #[should_panic(expected = "Event announcement exceeds max_onchain_bytes")]
+    fn test_post_event_exceeds_max_onchain_bytes() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.update_config(Config {
+            max_onchain_bytes: Some(64),
+            ..Config::default()
+        });
+
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        contract.post_event(
+            "Rust meetup".to_string(),
+            0,
+            "Lisbon".to_string(),
+            "Bring a laptop, we will pair on smart contracts all afternoon".to_string(),
+        );
+    }
+
+    /// Test that RSVPs need an existing event
+    #[test]
+    #[should_panic(expected = "Event not found")]
+    fn test_rsvp_to_regular_tweet() {
+        let context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        let tweet = contract.post_tweet("Not an event".to_string(), None, None, None, None);
+
+        contract.rsvp(tweet.id, RsvpStatus::Going);
+    }
+
+    /// Test the live spaces registry: create, co-hosts, listing and ending
+    /// Similar to testing the rooms API of a video conferencing backend
+    #[test]
+    fn test_spaces() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+
+        // Act: User 1 creates a space and invites user 2 to co-host
+        let space = contract.create_space(
+            "NEAR office hours".to_string(),
+            0,
+            "https://meet.example.com/near".to_string(),
+        );
+        let space = contract.add_space_cohost(space.id, accounts(2));
+
+        // Assert: The space is live and announced in the host's timeline
+        assert_eq!(space.cohosts, vec![accounts(2)]);
+        assert_eq!(contract.get_live_spaces(None, None), vec![space.clone()]);
+        let announcement = contract.get_tweet_by_id(space.announcement_id).unwrap();
+        assert_eq!(announcement.author, accounts(1));
+        assert!(announcement.text.contains("NEAR office hours"));
+        assert!(announcement.text.contains("https://meet.example.com/near"));
+
+        // Act: The host ends the space
+        context.block_timestamp(42);
+        testing_env!(context.build());
+        let ended = contract.end_space(space.id);
+
+        // Assert: It leaves the live list but can still be looked up
+        assert_eq!(ended.ended_at, Some(42));
+        assert!(contract.get_live_spaces(None, None).is_empty());
+        assert_eq!(contract.get_space(space.id), Some(ended));
+    }
+
+    /// Test that only the host can manage a space
+    #[test]
+    #[should_panic(expected = "Only the host can manage this space")]
+    fn test_end_space_requires_host() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        let space = contract.create_space("Room".to_string(), 0, "room-1".to_string());
+
+        context.predecessor_account_id(accounts(2));
+        testing_env!(context.build());
+        contract.end_space(space.id);
+    }
+
+    /// Test that space announcements must fit on-chain when hybrid storage is enabled
+    #[test]
+    #[should_panic(expected = "Space announcement exceeds max_onchain_bytes")]
+    fn test_create_space_exceeds_max_onchain_bytes() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.update_config(Config {
+            max_onchain_bytes: Some(64),
+            ..Config::default()
+        });
+
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        contract.create_space(
+            "NEAR office hours".to_string(),
+            0,
+            "https://meet.example.com/near".to_string(),
+        );
+    }
+
+    /// Test that co-hosts are frozen with every other write during a decommission
+    #[test]
+    #[should_panic(expected = "Contract is being decommissioned")]
+    fn test_add_space_cohost_while_decommissioning() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        let space = contract.create_space("Room".to_string(), 0, "room-1".to_string());
+
+        context.predecessor_account_id(accounts(0));
+        testing_env!(context.build());
+        contract.decommission();
+
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        contract.add_space_cohost(space.id, accounts(2));
+    }
+
+    /// Test that authors can't relabel tweets once a decommission is announced
+    #[test]
+    #[should_panic(expected = "Contract is being decommissioned: writes are frozen")]
+    fn test_set_adult_flag_while_decommissioning() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.post_tweet("Spicy".to_string(), None, None, None, None);
+
+        context.predecessor_account_id(accounts(0));
+        testing_env!(context.build());
+        contract.decommission();
+
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        contract.set_adult_flag(TweetId(0), true);
+    }
+
+    /// Test that moderators can't relabel tweets after the grace period, while the final
+    /// digest is being taken
+    #[test]
+    #[should_panic(expected = "Decommission grace period is over: adult labels can't change")]
+    fn test_moderate_adult_flag_after_grace_period() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.post_tweet("Spicy".to_string(), None, None, None, None);
+
+        context.predecessor_account_id(accounts(0));
+        testing_env!(context.build());
+        contract.decommission();
+        let decommission_after = contract.get_status().decommission_after.unwrap();
+
+        context.block_timestamp(decommission_after);
+        testing_env!(context.build());
+        contract.moderate_adult_flag(TweetId(0), true);
+    }
+
+    /// Test that legal holds can't change after the grace period, while the final digest
+    /// is being taken
+    #[test]
+    #[should_panic(expected = "Decommission grace period is over: legal holds can't change")]
+    fn test_freeze_tweet_after_grace_period() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.post_tweet("Disputed".to_string(), None, None, None, None);
+
+        context.predecessor_account_id(accounts(0));
+        testing_env!(context.build());
+        contract.decommission();
+        let decommission_after = contract.get_status().decommission_after.unwrap();
+
+        context.block_timestamp(decommission_after);
+        testing_env!(context.build());
+        contract.freeze_tweet(TweetId(0));
+    }
+
+    /// Test linking accounts to external identities through an attester
+    /// Similar to testing a verification service's callback into a user profile
+    #[test]
+    fn test_identity_attestation() {
+        // Setup: The owner trusts user 3 as an attester
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.update_config(Config {
+            attesters: vec![accounts(3)],
+            ..Config::default()
+        });
+
+        // Act: The attester links user 1 to a GitHub handle, then to a newer one
+        context.predecessor_account_id(accounts(3));
+        testing_env!(context.build());
+        let proof_hash = Base64VecU8::from(vec![7; 32]);
+        contract.attest_identity(
+            accounts(1),
+            IdentityPlatform::GitHub,
+            "@old-handle".to_string(),
+            proof_hash.clone(),
+        );
+        contract.attest_identity(
+            accounts(1),
+            IdentityPlatform::GitHub,
+            "alice".to_string(),
+            proof_hash.clone(),
+        );
+
+        // Assert: One link per platform, shown as a profile field on the Person
+        let identities = contract.get_linked_identities(accounts(1));
+        assert_eq!(identities.len(), 1);
+        assert_eq!(identities[0].handle, "alice");
+        assert_eq!(identities[0].attester, accounts(3));
+        assert_eq!(identities[0].proof_hash, proof_hash);
+        let person = contract.get_activitypub_person(accounts(1));
+        assert_eq!(person.attachment.len(), 1);
+        assert_eq!(person.attachment[0].name, "GitHub");
+        assert_eq!(person.attachment[0].value, "alice");
+
+        // Act & Assert: The account can unlink itself
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        contract.unlink_identity(IdentityPlatform::GitHub);
+        assert!(contract.get_linked_identities(accounts(1)).is_empty());
+    }
+
+    /// Test that only configured attesters can link identities
+    #[test]
+    #[should_panic(expected = "Only configured attesters can attest identities")]
+    fn test_attest_identity_requires_attester() {
+        let context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+
+        contract.attest_identity(
+            accounts(1),
+            IdentityPlatform::X,
+            "alice".to_string(),
+            Base64VecU8::from(vec![0; 32]),
+        );
+    }
+
+    /// Test receiving posts from a federation peer
+    /// Similar to testing a webhook endpoint that only accepts known partners
+    #[test]
+    fn test_receive_federated_post() {
+        // Setup: The owner configures user 3 as a peer contract
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.update_config(Config {
+            federation_peers: vec![accounts(3)],
+            ..Config::default()
+        });
+
+        // Tweets are still posted normally while forwarding is configured
+        contract.post_tweet("Hello peers".to_string(), None, None, None, None);
+
+        // Act: The peer forwards a post, claiming a different origin
+        context.predecessor_account_id(accounts(3));
+        testing_env!(context.build());
+        contract.receive_federated_post(FederatedPost {
+            origin: accounts(4),
+            tweet_id: TweetId(7),
+            author: accounts(1),
+            text: "Hello from another community".to_string(),
+            content_hash: vec![0; 32].into(),
+            timestamp: 0,
+        });
+
+        // Assert: The post is stored and tagged with the real origin
+        let posts = contract.get_federated_posts(None, None);
+        assert_eq!(posts.len(), 1);
+        assert_eq!(posts[0].origin, accounts(3));
+        assert_eq!(posts[0].tweet_id, TweetId(7));
+    }
+
+    /// Test that only configured peers can forward posts
+    #[test]
+    #[should_panic(expected = "Only federation peers can forward posts")]
+    fn test_receive_federated_post_from_unknown_account() {
+        let context = get_context(accounts(2));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+
+        contract.receive_federated_post(FederatedPost {
+            origin: accounts(2),
+            tweet_id: TweetId(0),
+            author: accounts(2),
+            text: "Spam".to_string(),
+            content_hash: vec![0; 32].into(),
+            timestamp: 0,
+        });
+    }
+
+    /// Test that the number of federation peers is capped (each tweet calls every peer)
+    #[test]
+    #[should_panic(expected = "Too many federation peers: at most 10 are allowed")]
+    fn test_update_config_too_many_federation_peers() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+
+        contract.update_config(Config {
+            federation_peers: (0..11)
+                .map(|i| format!("peer{}.testnet", i).parse().unwrap())
+                .collect(),
+            ..Config::default()
+        });
+    }
+
+    /// Test tweet deletion with authorization
+    /// Similar to testing DELETE /tweets/{id} with ownership verification
+    #[test]
+    fn test_delete_tweet() {
+        // Setup: Create a tweet from user 1
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.post_tweet("Tweet to delete".to_string(), None, None, None, None);
+
+        // Verify tweet exists
+        assert!(contract.get_tweet_by_id(TweetId(0)).is_some());
+
+        // Act: Delete the tweet as the author (should succeed)
+        contract.delete_tweet(TweetId(0));
+
+        // Assert: Tweet should be deleted
+        assert!(contract.get_tweet_by_id(TweetId(0)).is_none());
+
+        // Edge Case: Try to delete non-existent tweet (should not panic)
+        contract.delete_tweet(TweetId(999)); // Should handle gracefully
+
+        // Authorization Test: Create another tweet and try to delete as different user
+        contract.post_tweet("Another tweet".to_string(), None, None, None, None);
+
+        // Switch to different user context (like switching JWT token)
+        context.predecessor_account_id(accounts(2));
+        testing_env!(context.build());
+
+        // Act: Try to delete as different user (should fail)
+        contract.delete_tweet(TweetId(1));
+
+        // Switch back to original author to verify tweet still exists
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+
+        // Assert: Tweet should still exist (deletion should have failed)
+        assert!(contract.get_tweet_by_id(TweetId(1)).is_some());
+    }
+
+    // ============================================================================================
+    // OWNER OPERATION TESTS (Administrative methods)
+    // ============================================================================================
+
+    /// Test read-only maintenance mode
+    /// Similar to testing that POST endpoints return 503 while DELETE and GET keep working
+    #[test]
+    fn test_read_only_mode() {
+        // Setup: A user posts a tweet while the contract is active
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.post_tweet("Before maintenance".to_string(), None, None, None, None);
+        assert_eq!(contract.get_status().mode, ContractMode::Active);
+
+        // Act: The owner (contract account) enables read-only mode
+        context.predecessor_account_id(accounts(0));
+        testing_env!(context.build());
+        contract.set_mode(ContractMode::ReadOnly);
+        assert_eq!(contract.get_status().mode, ContractMode::ReadOnly);
+
+        // Assert: Views and deletes keep working for regular users
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        assert_eq!(contract.get_all_tweets(None, None, None).len(), 1);
+        contract.delete_tweet(TweetId(0));
+        assert!(contract.get_tweet_by_id(TweetId(0)).is_none());
+    }
+
+    /// Test that posting is rejected in read-only mode
+    #[test]
+    #[should_panic(expected = "Contract is in read-only mode")]
+    fn test_post_tweet_in_read_only_mode() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.set_mode(ContractMode::ReadOnly);
+
+        // Act: A regular user tries to post (should panic, like a 503 response)
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        contract.post_tweet("During maintenance".to_string(), None, None, None, None);
+    }
+
+    /// Test that only the owner can change the contract mode
+    /// Similar to testing that admin endpoints return 403 for regular users
+    #[test]
+    #[should_panic(expected = "Only the contract owner can call this method")]
+    fn test_set_mode_requires_owner() {
+        let context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+
+        contract.set_mode(ContractMode::ReadOnly);
+    }
+
+    /// Test an owner method going through 2-of-3 admin council approvals
+    /// Similar to testing a change request that needs two reviewers before it is deployed
+    #[test]
+    fn test_admin_council() {
+        // Setup: The owner hands owner methods to a 2-of-3 council
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.set_admin_council(vec![accounts(3), accounts(1), accounts(2)], 2);
+        assert_eq!(
+            contract.get_admin_council().unwrap().members,
+            vec![accounts(1), accounts(2), accounts(3)]
+        );
+
+        // Act: One member proposes read-only mode
+        context
+            .predecessor_account_id(accounts(1))
+            .signer_account_id(accounts(1));
+        testing_env!(context.build());
+        let proposal = contract.propose_admin_action(
+            "set_mode".to_string(),
+            r#"{"mode": "ReadOnly"}"#.to_string(),
+        );
+
+        // Assert: The proposer's approval alone isn't enough
+        assert_eq!(proposal.status, AdminProposalStatus::Pending);
+        assert_eq!(proposal.approvals, vec![accounts(1)]);
+
+        // Act: A second member approves
+        context
+            .predecessor_account_id(accounts(2))
+            .signer_account_id(accounts(2));
+        testing_env!(context.build());
+        let proposal = contract.approve(proposal.id);
+
+        // Assert: The threshold is reached and the call is dispatched with a fixed gas budget,
+        // followed by a callback that records its outcome
+        let proposal_id = proposal.id;
+        assert_eq!(proposal.status, AdminProposalStatus::Executing);
+        assert_eq!(contract.get_admin_proposal(proposal_id), Some(proposal));
+        assert!(get_logs()
+            .iter()
+            .any(|log| log.contains("calling set_mode")));
+        let receipts = near_sdk::test_utils::get_created_receipts();
+        let calls: Vec<(String, near_sdk::Gas)> = receipts
+            .iter()
+            .flat_map(|receipt| receipt.actions.iter())
+            .filter_map(|action| match action {
+                near_sdk::mock::MockAction::FunctionCallWeight {
+                    method_name,
+                    prepaid_gas,
+                    ..
+                } => Some((
+                    String::from_utf8(method_name.clone()).unwrap(),
+                    *prepaid_gas,
+                )),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            calls,
+            vec![
+                ("set_mode".to_string(), near_sdk::Gas::from_tgas(50)),
+                (
+                    "on_admin_proposal_executed".to_string(),
+                    near_sdk::Gas::from_tgas(5)
+                ),
+            ]
+        );
+
+        // Act: The dispatched call arrives from the contract itself, signed by the approver
+        context.predecessor_account_id(accounts(0));
+        testing_env!(context.build());
+        contract.set_mode(ContractMode::ReadOnly);
+
+        // Assert: The owner method ran
+        assert_eq!(contract.get_status().mode, ContractMode::ReadOnly);
+
+        // Act: The callback receives the call's successful result
+        testing_env!(
+            context.build(),
+            near_sdk::test_vm_config(),
+            near_sdk::RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![PromiseResult::Successful(Vec::new())]
+        );
+        assert!(contract.on_admin_proposal_executed(proposal_id));
+
+        // Assert: The proposal is marked as executed
+        assert_eq!(
+            contract.get_admin_proposal(proposal_id).unwrap().status,
+            AdminProposalStatus::Executed
+        );
+    }
+
+    /// Test that a failed admin proposal call is recorded and can't be approved again
+    /// Similar to testing that a failed deployment marks its change request as failed
+    #[test]
+    #[should_panic(expected = "Admin proposal was already executed")]
+    fn test_admin_proposal_failed_call() {
+        // Setup: A 1-of-2 council, so a proposal is dispatched right away
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.set_admin_council(vec![accounts(1), accounts(2)], 1);
+        context
+            .predecessor_account_id(accounts(1))
+            .signer_account_id(accounts(1));
+        testing_env!(context.build());
+        let proposal = contract.propose_admin_action(
+            "create_topic".to_string(),
+            r#"{"name": "", "description": ""}"#.to_string(),
+        );
+        assert_eq!(proposal.status, AdminProposalStatus::Executing);
+
+        // Act: The call panicked, and the callback receives the failure
+        context.predecessor_account_id(accounts(0));
+        testing_env!(
+            context.build(),
+            near_sdk::test_vm_config(),
+            near_sdk::RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![PromiseResult::Failed]
+        );
+        assert!(!contract.on_admin_proposal_executed(proposal.id));
+
+        // Assert: The proposal is marked as failed and stays closed
+        assert_eq!(
+            contract.get_admin_proposal(proposal.id).unwrap().status,
+            AdminProposalStatus::Failed
+        );
+        context
+            .predecessor_account_id(accounts(2))
+            .signer_account_id(accounts(2));
+        testing_env!(context.build());
+        contract.approve(proposal.id);
+    }
+
+    /// Test that the contract account's key can't call owner methods alone once a council exists
+    #[test]
+    #[should_panic(
+        expected = "Owner methods need admin council approval: use propose_admin_action"
+    )]
+    fn test_owner_method_requires_council_approval() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.set_admin_council(vec![accounts(1), accounts(2)], 2);
+
+        // Act: A transaction signed with the contract account's key
+        contract.set_mode(ContractMode::ReadOnly);
+    }
+
+    /// Test that only owner methods can be proposed
+    #[test]
+    #[should_panic(expected = "Not an owner method: post_tweet")]
+    fn test_propose_admin_action_requires_owner_method() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.set_admin_council(vec![accounts(1), accounts(2)], 2);
+
+        context
+            .predecessor_account_id(accounts(1))
+            .signer_account_id(accounts(1));
+        testing_env!(context.build());
+        contract.propose_admin_action("post_tweet".to_string(), r#"{"text": "Hi"}"#.to_string());
+    }
+
+    /// Test that replacing the council voids the old council's pending proposals
+    #[test]
+    #[should_panic(expected = "Admin proposal was made for a previous council")]
+    fn test_approve_proposal_of_previous_council() {
+        // Setup: A 2-of-2 council has a pending proposal
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.set_admin_council(vec![accounts(1), accounts(2)], 2);
+        context
+            .predecessor_account_id(accounts(1))
+            .signer_account_id(accounts(1));
+        testing_env!(context.build());
+        let stale = contract.propose_admin_action("create_snapshot".to_string(), "{}".to_string());
+
+        // Act: A council proposal replaces the council, then its new member approves the old one
+        let rotation = contract.propose_admin_action(
+            "set_admin_council".to_string(),
+            format!(
+                r#"{{"members": ["{}", "{}"], "threshold": 2}}"#,
+                accounts(1),
+                accounts(3)
+            ),
+        );
+        context
+            .predecessor_account_id(accounts(2))
+            .signer_account_id(accounts(2));
+        testing_env!(context.build());
+        contract.approve(rotation.id);
+        context.predecessor_account_id(accounts(0));
+        testing_env!(context.build());
+        contract.set_admin_council(vec![accounts(1), accounts(3)], 2);
+        context
+            .predecessor_account_id(accounts(3))
+            .signer_account_id(accounts(3));
+        testing_env!(context.build());
+        contract.approve(stale.id);
+    }
+
+    /// Test putting a tweet under a legal hold and releasing it
+    /// Similar to testing a litigation hold flag that blocks DELETE until it is lifted
+    #[test]
+    fn test_freeze_tweet() {
+        const DAY: u64 = 24 * 60 * 60 * 1_000_000_000;
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.update_settings(SettingsPatch {
+            expire_after_days: Some(1),
+            ..Default::default()
+        });
+        contract.post_tweet("Disputed".to_string(), None, None, None, None);
+
+        // Act: The owner freezes the tweet
+        context.predecessor_account_id(accounts(0));
+        testing_env!(context.build());
+        let frozen = contract.freeze_tweet(TweetId(0));
+
+        // Assert: Views show the hold, the audit log records it and expiry skips the tweet
+        assert!(frozen.frozen);
+        assert!(contract.get_tweet_by_id(TweetId(0)).unwrap().frozen);
+        let op = contract.get_op(2).unwrap();
+        assert_eq!(op.kind, OperationKind::FreezeTweet);
+        assert_eq!(op.account_id, accounts(0));
+        context.block_timestamp(2 * DAY);
+        testing_env!(context.build());
+        assert_eq!(contract.expire_tweets(10), 0);
+
+        // Act: The hold is released, then the author deletes the tweet
+        assert!(!contract.unfreeze_tweet(TweetId(0)).frozen);
+        assert_eq!(
+            contract.get_op(3).unwrap().kind,
+            OperationKind::UnfreezeTweet
+        );
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        contract.delete_tweet(TweetId(0));
+
+        // Assert
+        assert!(contract.get_tweet_by_id(TweetId(0)).is_none());
+    }
+
+    /// Test that the author can't delete a frozen tweet
+    /// Similar to testing that DELETE is refused while a legal hold is in place
+    #[test]
+    #[should_panic(expected = "Tweet is frozen by a legal hold")]
+    fn test_delete_frozen_tweet() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.post_tweet("Disputed".to_string(), None, None, None, None);
+        context.predecessor_account_id(accounts(0));
+        testing_env!(context.build());
+        contract.freeze_tweet(TweetId(0));
+
+        // Act
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        contract.delete_tweet(TweetId(0));
+    }
+
+    /// Test age-restricted labels and the explicit opt-in of list views
+    /// Similar to testing an NSFW flag that feeds filter out unless ?include_nsfw=true
+    #[test]
+    fn test_adult_content() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.post_tweet("gm everyone".to_string(), None, None, None, None);
+        contract.post_tweet("gm after dark".to_string(), None, None, None, None);
+        context.predecessor_account_id(accounts(2));
+        testing_env!(context.build());
+        contract.post_tweet(
+            "gm from a reported account".to_string(),
+            None,
+            None,
+            None,
+            None,
+        );
+
+        // Act: The author labels their own tweet, the owner labels a reported one
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        assert!(contract.set_adult_flag(TweetId(1), true).adult);
+        context.predecessor_account_id(accounts(0));
+        testing_env!(context.build());
+        contract.moderate_adult_flag(TweetId(2), true);
+
+        // Assert: List views leave labelled tweets out by default
+        let ids = |tweets: Vec<Tweet>| tweets.iter().map(|tweet| tweet.id.0).collect::<Vec<_>>();
+        assert_eq!(ids(contract.get_all_tweets(None, None, None)), vec![0]);
+        assert_eq!(
+            ids(contract.search_tweets("gm".to_string(), None, None, None)),
+            vec![0]
+        );
+        assert_eq!(
+            ids(contract.get_tweets_v2(None, None, None).tweets),
+            vec![0]
+        );
+
+        // Assert: An explicit opt-in returns them, and direct lookups show the label
+        assert_eq!(
+            ids(contract.get_tweets_v2(None, None, Some(true)).tweets),
+            vec![2, 1, 0]
+        );
+        assert_eq!(
+            ids(contract.get_tweets_by_author(accounts(1), None, None, Some(true))),
+            vec![0, 1]
+        );
+        assert!(contract.get_tweet_by_id(TweetId(1)).unwrap().adult);
+
+        // Act & Assert: The owner (as moderator) can remove a label
+        assert!(!contract.moderate_adult_flag(TweetId(1), false).adult);
+        assert_eq!(ids(contract.get_all_tweets(None, None, None)), vec![0, 1]);
+    }
+
+    /// Test that an author can't remove a moderator's label
+    /// Similar to testing that PATCH on a moderated field returns 403 for the resource owner
+    #[test]
+    #[should_panic(expected = "Only moderators can remove an adult label")]
+    fn test_author_cannot_remove_adult_label() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.post_tweet("Reported".to_string(), None, None, None, None);
+        context.predecessor_account_id(accounts(0));
+        testing_env!(context.build());
+        contract.moderate_adult_flag(TweetId(0), true);
+
+        // Act
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        contract.set_adult_flag(TweetId(0), false);
+    }
+
+    /// Test that the contract account can't moderate labels alone once an admin council exists
+    #[test]
+    #[should_panic(
+        expected = "Owner methods need admin council approval: use propose_admin_action"
+    )]
+    fn test_moderate_adult_flag_requires_council_approval() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.post_tweet("Reported".to_string(), None, None, None, None);
+        context
+            .predecessor_account_id(accounts(0))
+            .signer_account_id(accounts(0));
+        testing_env!(context.build());
+        contract.set_admin_council(vec![accounts(2), accounts(3)], 2);
+
+        // Act: A transaction signed with the contract account's key
+        contract.moderate_adult_flag(TweetId(0), true);
+    }
+
+    /// Test registering and unregistering listener contracts
+    /// Similar to testing CRUD on admin webhook subscriptions
+    #[test]
+    fn test_listeners() {
+        // Setup: The owner registers a bot for posted + liked events
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        let mask = TwitterEvent::TweetPosted.mask() | TwitterEvent::TweetLiked.mask();
+        contract.register_listener(accounts(3), mask);
+        assert_eq!(contract.get_listeners(), vec![(accounts(3), mask)]);
+
+        // Notifications are fire-and-forget: posting and liking work as usual
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        contract.post_tweet("Bots are listening".to_string(), None, None, None, None);
+        assert_eq!(contract.like_tweet(TweetId(0), None).unwrap().likes, 1);
+
+        // Act: The owner removes the listener
+        context.predecessor_account_id(accounts(0));
+        testing_env!(context.build());
+        contract.unregister_listener(accounts(3));
+        assert!(contract.get_listeners().is_empty());
+    }
+
+    /// Test that listeners can only subscribe to known events
+    #[test]
+    #[should_panic(expected = "Invalid event mask")]
+    fn test_register_listener_with_invalid_mask() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+
+        contract.register_listener(accounts(3), 8);
+    }
+
+    /// Test that every listener notification carries a fixed gas budget
+    /// Similar to testing that webhook deliveries use a fixed timeout instead of an open-ended one
+    #[test]
+    fn test_listener_notification_gas() {
+        // Setup: The owner registers a bot for posted events
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.register_listener(accounts(3), TwitterEvent::TweetPosted.mask());
+
+        // Act: A user posts a tweet
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        contract.post_tweet("Bots are listening".to_string(), None, None, None, None);
+
+        // Assert: The notification gets exactly 5 Tgas and no share of the leftover gas
+        let receipts = near_sdk::test_utils::get_created_receipts();
+        assert_eq!(receipts.len(), 1);
+        assert_eq!(receipts[0].receiver_id, accounts(3));
+        match &receipts[0].actions[0] {
+            near_sdk::mock::MockAction::FunctionCallWeight {
+                method_name,
+                prepaid_gas,
+                gas_weight,
+                ..
+            } => {
+                assert_eq!(method_name.as_slice(), b"on_twitter_event");
+                assert_eq!(*prepaid_gas, near_sdk::Gas::from_tgas(5));
+                assert_eq!(gas_weight.0, 0);
+            }
+            action => panic!("Unexpected action: {:?}", action),
+        }
+    }
+
+    /// Test the two-step decommission flow
+    /// Similar to testing a service sunset: announce, grace period, then final shutdown
+    #[test]
+    fn test_decommission() {
+        // Setup: A user has content on the platform
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.post_tweet("Goodbye soon".to_string(), None, None, None, None);
+        contract.post_tweet("Archive me".to_string(), None, None, None, None);
+
+        // Act: The owner announces the shutdown
+        context.predecessor_account_id(accounts(0));
+        testing_env!(context.build());
+        contract.decommission();
+
+        // Assert: The shutdown deadline is visible to clients
+        let decommission_after = contract
+            .get_status()
+            .decommission_after
+            .expect("Decommission should be scheduled");
+
+        // Assert: Users can still read and delete their content during the grace period
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        assert_eq!(contract.get_all_tweets(None, None, None).len(), 2);
+        contract.delete_tweet(TweetId(0));
+
+        // Act: After the grace period the owner takes the final digest in small batches
+        context
+            .predecessor_account_id(accounts(0))
+            .block_timestamp(decommission_after);
+        testing_env!(context.build());
+        assert_eq!(contract.digest_final_state(1), None);
+        let digest = contract
+            .digest_final_state(1)
+            .expect("Digest should be complete after scanning every id");
+
+        // Assert: The digest matches one recomputed off-chain from an export
+        let mut expected = vec![0u8; 32];
+        for tweet in contract.get_all_tweets(None, None, Some(true)) {
+            expected.extend(near_sdk::borsh::to_vec(&tweet).unwrap());
+            expected = near_sdk::env::sha256(&expected);
+        }
+        assert_eq!(digest.0, expected);
+        assert!(get_logs()
+            .iter()
+            .any(|log| log.starts_with("Final state snapshot: 1 tweets")));
+
+        // Act: The owner removes storage keys in batches, then deletes the account
+        near_sdk::env::storage_write(b"leftover", b"data");
+        let removed = contract.remove_state_keys(vec![
+            b"leftover".to_vec().into(),
+            b"missing".to_vec().into(),
+        ]);
+        assert_eq!(removed, 1);
+        assert!(!near_sdk::env::storage_has_key(b"leftover"));
+        contract.finalize_decommission(accounts(0));
+
+        // Assert: The deletion is in flight and a second one is refused until it resolves
+        let progress = contract
+            .get_status()
+            .decommission_progress
+            .expect("Progress should be visible to clients");
+        assert_eq!(progress.keys_removed, 1);
+        assert!(progress.deletion_pending);
+    }
+
+    /// Test that a failed account deletion is reported and can be retried
+    #[test]
+    fn test_finalize_decommission_failure_callback() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.decommission();
+        let decommission_after = contract.get_status().decommission_after.unwrap();
+        context.block_timestamp(decommission_after);
+        testing_env!(context.build());
+        contract.digest_final_state(10);
+        contract.finalize_decommission(accounts(0));
+
+        // Act: The runtime rejected the deletion (e.g. too much state left)
+        testing_env!(context.build());
+        let deleted = contract.on_decommission_finalized(Err(near_sdk::PromiseError::Failed));
+
+        // Assert: The failure is logged and the owner can try again
+        assert!(!deleted);
+        assert!(get_logs()[0].starts_with("Account deletion failed"));
+        assert!(
+            !contract
+                .get_status()
+                .decommission_progress
+                .unwrap()
+                .deletion_pending
+        );
+        contract.finalize_decommission(accounts(0));
+    }
+
+    /// Test that an announced shutdown can be called off before any state is removed
+    #[test]
+    fn test_cancel_decommission() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.decommission();
+        let decommission_after = contract.get_status().decommission_after.unwrap();
+        context.block_timestamp(decommission_after);
+        testing_env!(context.build());
+        contract.digest_final_state(10);
+
+        // Act: The owner changes their mind after taking the digest
+        contract.cancel_decommission();
+
+        // Assert: Nothing is scheduled any more and writes work again
+        let status = contract.get_status();
+        assert_eq!(status.decommission_after, None);
+        assert_eq!(status.decommission_progress, None);
+        contract.post_tweet("Still here".to_string(), None, None, None, None);
+    }
+
+    /// Test that the shutdown can't be cancelled once state removal has started
+    #[test]
+    #[should_panic(expected = "State removal has started: the decommission can't be cancelled")]
+    fn test_cancel_decommission_after_state_removal() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.decommission();
+        let decommission_after = contract.get_status().decommission_after.unwrap();
+        context.block_timestamp(decommission_after);
+        testing_env!(context.build());
+        contract.digest_final_state(10);
+        near_sdk::env::storage_write(b"leftover", b"data");
+        contract.remove_state_keys(vec![b"leftover".to_vec().into()]);
+
+        contract.cancel_decommission();
+    }
+
+    /// Test that the contract's own state record can't be removed piecemeal
+    #[test]
+    #[should_panic(expected = "The contract state record is removed together with the account")]
+    fn test_remove_state_keys_refuses_state_record() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.decommission();
+        let decommission_after = contract.get_status().decommission_after.unwrap();
+        context.block_timestamp(decommission_after);
+        testing_env!(context.build());
+        contract.digest_final_state(10);
+
+        contract.remove_state_keys(vec![b"STATE".to_vec().into()]);
+    }
+
+    /// Test that writes are frozen once a decommission has been announced
+    #[test]
+    #[should_panic(expected = "Contract is being decommissioned")]
+    fn test_like_tweet_while_decommissioning() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.post_tweet("Frozen tweet".to_string(), None, None, None, None);
+
+        context.predecessor_account_id(accounts(0));
+        testing_env!(context.build());
+        contract.decommission();
+
+        // Act: Liking is a write, so it should be rejected
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        contract.like_tweet(TweetId(0), None);
+    }
+
+    /// Test that the account can't be deleted before the grace period ends (timelock)
+    #[test]
+    #[should_panic(expected = "Decommission grace period has not ended yet")]
+    fn test_finalize_decommission_before_grace_period() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.decommission();
+
+        contract.finalize_decommission(accounts(0));
+    }
+
+    // ============================================================================================
+    // READ OPERATION TESTS (Methods that don't modify state)
+    // ============================================================================================
+
+    /// Test retrieving specific tweet by ID
+    /// Similar to testing GET /tweets/{id} endpoint
+    #[test]
+    fn test_get_tweet_by_id() {
+        // Setup: Create a contract and post a tweet
+        let context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        let posted_tweet = contract.post_tweet("Test tweet".to_string(), None, None, None, None);
+
+        // Act: Retrieve the tweet by ID
+        let retrieved_tweet = contract.get_tweet_by_id(TweetId(0));
+
+        // Assert: Should return the correct tweet
+        assert!(retrieved_tweet.is_some());
+        assert_eq!(retrieved_tweet.unwrap(), posted_tweet.value);
+
+        // Edge Case: Try to get non-existent tweet
+        let non_existent = contract.get_tweet_by_id(TweetId(999));
+        assert!(non_existent.is_none()); // Should return None (like 404)
+    }
+
+    /// Test verifying an off-chain copy of a tweet against its on-chain hash
+    /// Similar to comparing a file's checksum with the published one
+    #[test]
+    fn test_verify_tweet_content() {
+        let context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.post_tweet(
+            "Hello NEAR!\nSecond line".to_string(),
+            None,
+            None,
+            None,
+            None,
+        );
+
+        // Assert: Exact copies and copies differing only in line endings/padding verify
+        assert!(contract.verify_tweet_content(TweetId(0), "Hello NEAR!\nSecond line".to_string()));
+        assert!(
+            contract.verify_tweet_content(TweetId(0), "  Hello NEAR!\r\nSecond line\n".to_string())
+        );
+
+        // Assert: Altered copies and unknown tweets don't verify
+        assert!(!contract.verify_tweet_content(TweetId(0), "Hello NEAR?\nSecond line".to_string()));
+        assert!(
+            !contract.verify_tweet_content(TweetId(999), "Hello NEAR!\nSecond line".to_string())
+        );
+    }
+
+    /// Test keyword search over the inverted index
+    /// Similar to testing GET /search?q=... against a full-text index
+    #[test]
+    fn test_search_tweets() {
+        // Setup: Post a few tweets with overlapping words
+        let context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.post_tweet(
+            "Learning #NEAR with Rust".to_string(),
+            None,
+            None,
+            None,
+            None,
+        );
+        contract.post_tweet("NEAR is fast".to_string(), None, None, None, None);
+        contract.post_tweet("Rust is fun".to_string(), None, None, None, None);
+
+        // Assert: Single-term search is case-insensitive and returns newest first
+        let near_results = contract.search_tweets("near".to_string(), None, None, None);
+        assert_eq!(near_results.len(), 2);
+        assert_eq!(near_results[0].text, "NEAR is fast");
+        assert_eq!(near_results[1].text, "Learning #NEAR with Rust");
+
+        // Assert: Multiple terms are combined with AND
+        let both = contract.search_tweets("Rust NEAR".to_string(), None, None, None);
+        assert_eq!(both.len(), 1);
+        assert_eq!(both[0].id, TweetId(0));
+
+        // Assert: Stopword-only and unknown queries match nothing
+        assert!(contract
+            .search_tweets("the is".to_string(), None, None, None)
+            .is_empty());
+        assert!(contract
+            .search_tweets("solidity".to_string(), None, None, None)
+            .is_empty());
+
+        // Assert: Deleted tweets disappear from results
+        contract.delete_tweet(TweetId(1));
+        assert_eq!(
+            contract
+                .search_tweets("near".to_string(), None, None, None)
+                .len(),
+            1
+        );
+    }
+
+    /// Test that posting lists are capped at max_postings_per_term (oldest dropped first)
+    #[test]
+    fn test_search_index_size_limit() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.update_config(Config {
+            max_postings_per_term: 2,
+            ..Config::default()
+        });
+
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        contract.post_tweet("gm 1".to_string(), None, None, None, None);
+        contract.post_tweet("gm 2".to_string(), None, None, None, None);
+        contract.post_tweet("gm 3".to_string(), None, None, None, None);
+
+        // Assert: Only the two newest tweets are still searchable
+        let results = contract.search_tweets("gm".to_string(), None, None, None);
+        let ids: Vec<u64> = results.iter().map(|tweet| tweet.id.0).collect();
+        assert_eq!(ids, vec![2, 1]);
+    }
+
+    /// Test incremental cleanup of search index entries of deleted tweets
+    /// Similar to testing a background job that drains a cleanup queue in batches
+    #[test]
+    fn test_gc_indexes() {
+        let context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        for text in ["rust one", "rust two", "rust three"] {
+            contract.post_tweet(text.to_string(), None, None, None, None);
+        }
+        contract.delete_tweet(TweetId(0));
+        contract.delete_tweet(TweetId(1));
+
+        // Act & Assert: Each call processes at most `limit` deleted tweets
+        assert_eq!(contract.gc_indexes(1), 1);
+        assert_eq!(contract.gc_indexes(10), 0);
+        assert_eq!(contract.gc_indexes(10), 0); // Nothing left, safe to repeat
+
+        // Assert: Search results are unchanged
+        let results = contract.search_tweets("rust".to_string(), None, None, None);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, TweetId(2));
+    }
+
+    /// Test that deleted off-chain tweets are cleaned out of the search index too
+    #[test]
+    fn test_gc_indexes_off_chain_tweet() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.update_config(Config {
+            max_onchain_bytes: Some(10),
+            ..Config::default()
+        });
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        let tweet = contract.post_tweet(
+            "A long tweet about rust kept off-chain".to_string(),
+            None,
+            Some("ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi".to_string()),
+            None,
+            None,
+        );
+        assert_eq!(
+            contract.search_tweets("rust".to_string(), None, None, None),
+            vec![tweet.value.clone()]
+        );
+
+        // Act: Delete it - its text is gone, but its terms were kept aside
+        contract.delete_tweet(tweet.id);
+
+        // Assert: The deletion was queued for cleanup and the cleanup finds its entries
+        assert_eq!(contract.get_status().gc_pending, 1);
+        assert_eq!(contract.gc_indexes(10), 0);
+        assert_eq!(contract.get_status().gc_pending, 0);
+        assert!(contract
+            .search_tweets("rust".to_string(), None, None, None)
+            .is_empty());
+    }
+
+    /// Test the auto-expiry setting and the sweep that enforces it
+    /// Similar to testing a cron job that purges rows past a per-user retention period
+    #[test]
+    fn test_expire_tweets() {
+        const DAY: u64 = 24 * 60 * 60 * 1_000_000_000;
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        let settings = contract.update_settings(SettingsPatch {
+            expire_after_days: Some(1),
+            ..Default::default()
+        });
+        assert_eq!(settings.expire_after_days, Some(1));
+        contract.post_tweet("Ephemeral one".to_string(), None, None, None, None);
+        contract.post_tweet("Ephemeral two".to_string(), None, None, None, None);
+        context.predecessor_account_id(accounts(2));
+        testing_env!(context.build());
+        contract.post_tweet("Kept forever".to_string(), None, None, None, None);
+
+        // Act: A day later user 1 posts again and a keeper runs the sweep in small batches
+        context
+            .predecessor_account_id(accounts(1))
+            .block_timestamp(DAY);
+        testing_env!(context.build());
+        contract.post_tweet("Fresh".to_string(), None, None, None, None);
+        assert_eq!(contract.expire_tweets(1), 1);
+        assert_eq!(contract.get_status().expiry_cursor, 1);
+        assert_eq!(contract.expire_tweets(10), 1);
+
+        // Assert: Only user 1's day-old tweets are gone, each with a deletion event
+        let ids: Vec<u64> = contract
+            .get_all_tweets(None, None, None)
+            .iter()
+            .map(|tweet| tweet.id.0)
+            .collect();
+        assert_eq!(ids, vec![3, 2]);
+        let deletions = event_logs()
+            .iter()
+            .filter(|event| event["event"] == "tweet_deleted")
+            .count();
+        assert_eq!(deletions, 2);
+
+        // Assert: 0 turns expiry off, so nothing else is deleted later
+        let settings = contract.update_settings(SettingsPatch {
+            expire_after_days: Some(0),
+            ..Default::default()
+        });
+        assert_eq!(settings.expire_after_days, None);
+        context.block_timestamp(3 * DAY);
+        testing_env!(context.build());
+        assert_eq!(contract.expire_tweets(10), 0);
+    }
+
+    /// Test daily activity counters
+    /// Similar to testing a GET /stats endpoint backed by a daily rollup table
+    #[test]
+    fn test_get_activity_stats() {
+        const DAY: u64 = 24 * 60 * 60 * 1_000_000_000;
+
+        // Day 0: user 1 posts twice and likes once
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.post_tweet("Day zero".to_string(), None, None, None, None);
+        contract.post_tweet("Still day zero".to_string(), None, None, None, None);
+        contract.like_tweet(TweetId(0), None);
+
+        // Day 1: user 2 joins and likes, user 1 posts again
+        context
+            .predecessor_account_id(accounts(2))
+            .block_timestamp(DAY);
+        testing_env!(context.build());
+        contract.like_tweet(TweetId(1), None);
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        contract.post_tweet("Day one".to_string(), None, None, None, None);
+
+        // Assert: Counters are bucketed per day, and quiet days are zero-filled
+        let stats = contract.get_activity_stats(0, 2);
+        assert_eq!(stats.len(), 3);
+        assert_eq!(
+            (
+                stats[0].tweets_posted,
+                stats[0].likes,
+                stats[0].new_accounts
+            ),
+            (2, 1, 1)
+        );
+        assert_eq!(
+            (
+                stats[1].tweets_posted,
+                stats[1].likes,
+                stats[1].new_accounts
+            ),
+            (1, 1, 1)
+        );
+        assert_eq!(stats[2].day, 2);
+        assert_eq!(stats[2].tweets_posted, 0);
+    }
+
+    /// Test weekly engagement rollups per author
+    /// Similar to testing a creator dashboard endpoint backed by a weekly rollup table
+    #[test]
+    fn test_get_weekly_rollups() {
+        const WEEK: u64 = 7 * 24 * 60 * 60 * 1_000_000_000;
+
+        // Week 0: user 1 posts twice, user 2 likes one of them
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.post_tweet("Week zero".to_string(), None, None, None, None);
+        contract.post_tweet("Still week zero".to_string(), None, None, None, None);
+        context.predecessor_account_id(accounts(2));
+        testing_env!(context.build());
+        contract.like_tweet(TweetId(0), None);
+
+        // Week 2: the old tweet gets another like
+        context.block_timestamp(2 * WEEK);
+        testing_env!(context.build());
+        contract.like_tweet(TweetId(0), None);
+
+        // Assert: Likes count in the week they were given, and quiet weeks are zero-filled
+        let rollups = contract.get_weekly_rollups(accounts(1), 0, 2);
+        let counts: Vec<(u32, u64, u64)> = rollups
+            .iter()
+            .map(|rollup| (rollup.week, rollup.posts, rollup.likes_received))
+            .collect();
+        assert_eq!(counts, vec![(0, 2, 1), (1, 0, 0), (2, 0, 1)]);
+
+        // Assert: Likes given don't count for the liker
+        assert_eq!(
+            contract.get_weekly_rollups(accounts(2), 0, 0)[0].likes_received,
+            0
+        );
+        assert!(contract.get_weekly_rollups(accounts(1), 3, 2).is_empty());
+    }
+
+    /// Test the per-persona breakdown of weekly rollups
+    /// Similar to testing an organization's analytics next to its owner's personal dashboard
+    #[test]
+    fn test_get_persona_weekly_rollups() {
+        // Setup: User 1 posts once personally and once as the "acme" persona
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.create_persona("acme".to_string());
+        contract.post_tweet("Personal".to_string(), None, None, None, None);
+        let persona_tweet = contract
+            .post_as_persona(
+                "acme".to_string(),
+                "Company news".to_string(),
+                None,
+                None,
+                None,
+                None,
+            )
+            .value;
+
+        // Act: User 2 likes the persona's tweet
+        context.predecessor_account_id(accounts(2));
+        testing_env!(context.build());
+        contract.like_tweet(persona_tweet.id, None);
+
+        // Assert: The owner's rollup counts both posts, the persona's only its own
+        let owner = &contract.get_weekly_rollups(accounts(1), 0, 0)[0];
+        assert_eq!((owner.posts, owner.likes_received), (2, 1));
+        let persona = &contract.get_persona_weekly_rollups("acme".to_string(), 0, 0)[0];
+        assert_eq!((persona.posts, persona.likes_received), (1, 1));
+        assert_eq!(
+            contract.get_persona_weekly_rollups("other".to_string(), 0, 0)[0].posts,
+            0
+        );
+    }
+
+    /// Test ranking tweets by recent likes
+    /// Similar to testing a "rising now" endpoint backed by sliding-window counters
+    #[test]
+    fn test_get_rising_tweets() {
+        // Setup: Three tweets at block 0, two likes for the first and one for the second
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        for text in ["First", "Second", "Third"] {
+            contract.post_tweet(text.to_string(), None, None, None, None);
+        }
+        context
+            .predecessor_account_id(accounts(2))
+            .block_height(100);
+        testing_env!(context.build());
+        contract.like_tweet(TweetId(0), None);
+        contract.like_tweet(TweetId(1), None);
+        context.predecessor_account_id(accounts(3));
+        testing_env!(context.build());
+        contract.like_tweet(TweetId(0), None);
+
+        // Act: Much later the third tweet gets a single like
+        context.block_height(10_000);
+        testing_env!(context.build());
+        contract.like_tweet(TweetId(2), None);
+
+        // Assert: The default window (about an hour) only sees the latest like
+        let rising = contract.get_rising_tweets(None, None, None);
+        assert_eq!(rising.len(), 1);
+        assert_eq!(rising[0].tweet.id, TweetId(2));
+        assert_eq!(rising[0].recent_likes, 1);
+
+        // Assert: A day-long window sees all likes; equal scores go to the newer tweet
+        let rising = contract.get_rising_tweets(Some(86_400), None, None);
+        let ranked: Vec<(TweetId, u64, u64)> = rising
+            .iter()
+            .map(|entry| (entry.tweet.id, entry.recent_likes, entry.score))
+            .collect();
+        assert_eq!(
+            ranked,
+            vec![
+                (TweetId(0), 2, 1000),
+                (TweetId(2), 1, 500),
+                (TweetId(1), 1, 500)
+            ]
+        );
+
+        // Act: A like a day later reuses the first bucket's ring slot
+        context.block_height(144 * 600 + 100);
+        testing_env!(context.build());
+        contract.like_tweet(TweetId(1), None);
+
+        // Assert: The overwritten bucket's likes are gone, not attributed to the new bucket
+        let rising = contract.get_rising_tweets(Some(86_400), None, None);
+        let ranked: Vec<(TweetId, u64)> = rising
+            .iter()
+            .map(|entry| (entry.tweet.id, entry.recent_likes))
+            .collect();
+        assert_eq!(ranked, vec![(TweetId(2), 1), (TweetId(1), 1)]);
+    }
+
+    /// Test the ranked feed and its configurable weights
+    /// Similar to testing a "top posts" sort whose weights live in a config table
+    #[test]
+    fn test_get_ranked_feed() {
+        const HOUR: u64 = 60 * 60 * 1_000_000_000;
+
+        // Setup: An old tweet with three likes, then a fresh tweet six hours later
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.post_tweet("Old but loved".to_string(), None, None, None, None);
+        for liker in [accounts(2), accounts(3), accounts(4)] {
+            context.predecessor_account_id(liker);
+            testing_env!(context.build());
+            contract.like_tweet(TweetId(0), None);
+        }
+        context.block_timestamp(6 * HOUR);
+        testing_env!(context.build());
+        contract.post_tweet("Fresh".to_string(), None, None, None, None);
+
+        // Assert: With the default weights the old tweet has decayed to half its points
+        let ranked: Vec<(TweetId, u64)> = contract
+            .get_ranked_feed(None, None)
+            .iter()
+            .map(|entry| (entry.tweet.id, entry.score))
+            .collect();
+        assert_eq!(ranked, vec![(TweetId(1), 1000), (TweetId(0), 650)]);
+
+        // Act: The owner makes likes count ten times more
+        context.predecessor_account_id(accounts(0));
+        testing_env!(context.build());
+        contract.update_config(Config {
+            ranking: RankingConfig {
+                like_weight: 1000,
+                recency_half_life: 6 * HOUR,
+            },
+            ..Config::default()
+        });
+
+        // Assert: The liked tweet now ranks first
+        let ranked = contract.get_ranked_feed(Some(1), None);
+        assert_eq!(ranked.len(), 1);
+        assert_eq!(ranked[0].tweet.id, TweetId(0));
+        assert_eq!(ranked[0].score, 2000);
+
+        // Act: The author labels the liked tweet as adult content
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        contract.set_adult_flag(TweetId(0), true);
+
+        // Assert: It is only ranked when the caller opts in
+        assert_eq!(
+            contract.get_ranked_feed(Some(1), None)[0].tweet.id,
+            TweetId(1)
+        );
+        assert_eq!(
+            contract.get_ranked_feed(Some(1), Some(true))[0].tweet.id,
+            TweetId(0)
+        );
+    }
+
+    /// Test the composite explore view
+    /// Similar to testing a backend-for-frontend endpoint that aggregates several services
+    #[test]
+    fn test_get_explore() {
+        // Setup: Two topics, a Rust tweet, a DeFi tweet and an adult-labelled DeFi tweet
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        let rust = contract.create_topic("Rust".to_string(), "All things Rust".to_string());
+        let defi = contract.create_topic("DeFi".to_string(), "Decentralized finance".to_string());
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        contract.post_tweet("Traits".to_string(), None, None, Some(vec![rust.id]), None);
+        contract.post_tweet("Yields".to_string(), None, None, Some(vec![defi.id]), None);
+        contract.post_tweet("Degen".to_string(), None, None, Some(vec![defi.id]), None);
+        contract.set_adult_flag(TweetId(2), true);
+
+        // Act: The DeFi tweets get two likes each, the Rust tweet one
+        for liker in [accounts(2), accounts(3)] {
+            context.predecessor_account_id(liker);
+            testing_env!(context.build());
+            contract.like_tweet(TweetId(1), None);
+            contract.like_tweet(TweetId(2), None);
+        }
+        contract.like_tweet(TweetId(0), None);
+
+        // Assert: Adult tweets are left out by default
+        let explore = contract.get_explore(None, None);
+        let rising: Vec<TweetId> = explore.rising.iter().map(|entry| entry.tweet.id).collect();
+        assert_eq!(rising, vec![TweetId(1), TweetId(0)]);
+        let trending: Vec<(u32, u64)> = explore
+            .trending_topics
+            .iter()
+            .map(|trending| (trending.topic.id, trending.recent_likes))
+            .collect();
+        assert_eq!(trending, vec![(defi.id, 2), (rust.id, 1)]);
+
+        // Assert: Opting in counts the adult tweet too, and the limit applies to every section
+        let explore = contract.get_explore(Some(1), Some(true));
+        assert_eq!(explore.rising.len(), 1);
+        assert_eq!(explore.rising[0].tweet.id, TweetId(2));
+        assert_eq!(explore.trending_topics.len(), 1);
+        assert_eq!(explore.trending_topics[0].recent_likes, 4);
+    }
+
+    /// Test the operation log used for optimistic UI reconciliation
+    /// Similar to testing that every write shows up in an audit log
+    #[test]
+    fn test_get_op() {
+        // Setup: Post, like and delete a tweet
+        let context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.post_tweet("Logged".to_string(), None, None, None, None);
+        contract.like_tweet(TweetId(0), None);
+        let delete_op = contract.delete_tweet(TweetId(0)).unwrap();
+
+        // Assert: Writes get increasing op ids, in execution order
+        assert_eq!(delete_op, 2);
+        let post = contract.get_op(0).unwrap();
+        assert_eq!(post.kind, OperationKind::PostTweet);
+        assert_eq!(post.account_id, accounts(1));
+        assert_eq!(post.target, Some(OpTarget::Tweet(TweetId(0))));
+        assert_eq!(contract.get_op(1).unwrap().kind, OperationKind::LikeTweet);
+        assert_eq!(
+            contract.get_op(delete_op).unwrap().kind,
+            OperationKind::DeleteTweet
+        );
+
+        // Assert: Failed writes don't get an op id
+        assert_eq!(contract.delete_tweet(TweetId(0)), None);
+        assert!(contract.get_op(3).is_none());
+    }
+
+    /// Test that writes return their op id and that every kind of write is logged
+    /// Similar to testing that a write API returns the id of its audit log entry
+    #[test]
+    fn test_get_op_for_every_write() {
+        let context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+
+        // Act: Posts and likes return their op ids next to the record
+        let tweet = contract.post_tweet("Logged".to_string(), None, None, None, None);
+        let like = contract.like_tweet(tweet.id, None).unwrap();
+        assert_eq!((tweet.op_id, like.op_id), (0, 1));
+        assert_eq!(like.likes, 1);
+
+        // Act: Other writes are logged too
+        contract.update_settings(SettingsPatch {
+            language: Some("en".to_string()),
+            ..Default::default()
+        });
+        contract.create_persona("acme".to_string());
+        let code = contract.register_short_link("https://near.org".to_string());
+        contract.record_click(code.clone());
+        contract.set_adult_flag(tweet.id, true);
+
+        // Assert: Each write has a typed target
+        let ops: Vec<_> = (2..7)
+            .map(|op_id| contract.get_op(op_id).unwrap())
+            .collect();
+        assert_eq!(ops[0].kind, OperationKind::UpdateSettings);
+        assert_eq!(ops[0].target, None);
+        assert_eq!(ops[1].target, Some(OpTarget::Persona("acme".to_string())));
+        assert_eq!(ops[2].kind, OperationKind::RegisterShortLink);
+        assert_eq!(ops[3].target, Some(OpTarget::ShortLink(code)));
+        assert_eq!(ops[4].kind, OperationKind::SetAdultFlag);
+        assert_eq!(ops[4].target, Some(OpTarget::Tweet(tweet.id)));
+    }
+
+    /// Test that retried writes with the same idempotency key are applied once
+    /// Similar to testing an API that honors the Idempotency-Key header
+    #[test]
+    fn test_idempotency_keys() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+
+        // Act: A relayer submits the same post twice
+        let key = Some("post-1".to_string());
+        let first = contract.post_tweet("Only once".to_string(), None, None, None, key.clone());
+        let retry = contract.post_tweet("Only once".to_string(), None, None, None, key);
+
+        // Assert: The retry returns the original tweet instead of a duplicate
+        assert_eq!(retry, first);
+        assert_eq!(contract.get_all_tweets(None, None, None).len(), 1);
+
+        // Act & Assert: Retried likes are counted once
+        let like_key = Some("like-1".to_string());
+        contract.like_tweet(first.id, like_key.clone());
+        assert_eq!(contract.like_tweet(first.id, like_key).unwrap().likes, 1);
+
+        // Assert: Keys are scoped per account - another user can reuse the same key
+        context.predecessor_account_id(accounts(2));
+        testing_env!(context.build());
+        let other = contract.post_tweet(
+            "Mine".to_string(),
+            None,
+            None,
+            None,
+            Some("post-1".to_string()),
+        );
+        assert_ne!(other.id, first.id);
+    }
+
+    /// Test that NEP-297 events carry block metadata and a per-block sequence index
+    /// Similar to testing that messages published to a queue can be ordered by consumers
+    #[test]
+    fn test_event_metadata() {
+        let mut context = get_context(accounts(1));
+        context.block_height(10).block_timestamp(1_000);
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+
+        // Act: Two events in block 10, one in block 11
+        contract.post_tweet("First".to_string(), None, None, None, None);
+        contract.like_tweet(TweetId(0), None);
+        let block_10 = event_logs();
+        context.block_height(11);
+        testing_env!(context.build());
+        contract.delete_tweet(TweetId(0));
+        let block_11 = event_logs();
+
+        // Assert: Events are indexed within their block
+        assert_eq!(block_10.len(), 2);
+        assert_eq!(block_10[0]["event"], "tweet_posted");
+        assert_eq!(block_10[0]["data"][0]["block_height"], 10);
+        assert_eq!(block_10[0]["data"][0]["block_timestamp"], 1_000);
+        assert_eq!(block_10[0]["data"][0]["event_index"], 0);
+        assert_eq!(block_10[1]["data"][0]["event_index"], 1);
+        assert_eq!(block_11[0]["event"], "tweet_deleted");
+        assert_eq!(block_11[0]["data"][0]["event_index"], 0);
+    }
+
+    /// Test the state invariant checker on a healthy contract
+    /// Similar to running a database consistency check after a migration
+    #[test]
+    fn test_check_invariants() {
+        let context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        for text in ["one", "two", "three"] {
+            contract.post_tweet(text.to_string(), None, None, None, None);
+        }
+        contract.delete_tweet(TweetId(1));
+
+        // Assert: No violations, and the sample is capped by the number of tweets
+        let report = contract.check_invariants(10, None);
+        assert_eq!(report.tweets_checked, 2);
+        assert_eq!(report.next_index, None);
+        assert!(report.violations.is_empty(), "{:?}", report.violations);
+
+        // Act: Two deletes are waiting for cleanup, and the queue is checked one at a time
+        contract.delete_tweet(TweetId(2));
+        let first_page = contract.check_invariants(1, None);
+        assert_eq!(first_page.next_index, Some(1));
+        assert!(
+            first_page.violations.is_empty(),
+            "{:?}",
+            first_page.violations
+        );
+        let second_page = contract.check_invariants(1, first_page.next_index);
+        assert_eq!(second_page.next_index, None);
+        assert!(
+            second_page.violations.is_empty(),
+            "{:?}",
+            second_page.violations
+        );
+    }
+
+    /// Test that the invariant checker reports search index entries of missing tweets
+    /// Similar to a consistency check finding index rows that point to no table row
+    #[test]
+    fn test_check_invariants_reports_corrupt_index() {
+        // Setup: Two tweets share the word "hello", and the state is written out
+        let context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.post_tweet("hello one".to_string(), None, None, None, None);
+        contract.post_tweet("hello two".to_string(), None, None, None, None);
+        near_sdk::env::state_write(&contract);
+        drop(contract);
+
+        // Act: Corrupt the index - "hello" now also points to a tweet that was never posted
+        // Search index entries are stored under "w" + the borsh-encoded word
+        let key = [b"w".as_slice(), &near_sdk::borsh::to_vec("hello").unwrap()].concat();
+        let postings = vec![TweetId(0), TweetId(1), TweetId(7)];
+        near_sdk::env::storage_write(&key, &near_sdk::borsh::to_vec(&postings).unwrap());
+        let contract: TwitterContract = near_sdk::env::state_read().unwrap();
+
+        // Assert: The dangling entry is reported
+        let report = contract.check_invariants(10, None);
+        assert_eq!(
+            report.violations,
+            vec![
+                "Search index entry 'hello' references tweet #7 that was never posted".to_string()
+            ]
+        );
+    }
+
+    /// Test the per-account token bucket on posts and likes
+    /// Similar to testing an API gateway's per-user rate limit and its refill
+    #[test]
+    fn test_rate_limit() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        let limit = RateLimitConfig {
+            capacity: 2,
+            refill_interval: 10_000,
+        };
+        contract.update_config(Config {
+            rate_limit: Some(limit.clone()),
+            ..Config::default()
+        });
+        assert_eq!(contract.get_rate_limit_config(), Some(limit));
+
+        // Act: A burst of two writes empties the bucket
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        let tweet = contract.post_tweet("One".to_string(), None, None, None, None);
+        contract.like_tweet(tweet.id, None);
+
+        // Act: One refill interval later the account has earned one more write
+        let key = Some("retry-me".to_string());
+        context.block_timestamp(12_000);
+        testing_env!(context.build());
+        contract.post_tweet("Two".to_string(), None, None, None, key.clone());
+
+        // Assert: Retries don't use tokens and other accounts have their own bucket
+        contract.post_tweet("Two".to_string(), None, None, None, key);
+        context.predecessor_account_id(accounts(2));
+        testing_env!(context.build());
+        contract.post_tweet("Other".to_string(), None, None, None, None);
+        assert_eq!(
+            contract.get_rate_limit_metrics(),
+            RateLimitMetrics {
+                tracked_accounts: 2,
+                metered_writes: 4,
+            }
+        );
+    }
+
+    /// Test that writes fail once the bucket is empty
+    /// Similar to testing that an API returns 429 Too Many Requests
+    #[test]
+    #[should_panic(expected = "Rate limit exceeded: try again later")]
+    fn test_rate_limit_exceeded() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.update_config(Config {
+            rate_limit: Some(RateLimitConfig {
+                capacity: 2,
+                refill_interval: 10_000,
+            }),
+            ..Config::default()
+        });
+        context
+            .predecessor_account_id(accounts(1))
+            .block_timestamp(0);
+        testing_env!(context.build());
+        contract.post_tweet("One".to_string(), None, None, None, None);
+        contract.post_tweet("Two".to_string(), None, None, None, None);
+
+        // Act: 9.999 of the 10 microseconds needed for a refill have passed
+        context.block_timestamp(9_999);
+        testing_env!(context.build());
+        contract.post_tweet("Three".to_string(), None, None, None, None);
+    }
+
+    /// Test that batch writes cost one token per item and failed likes cost nothing
+    /// Similar to testing a rate limiter that weighs bulk endpoints by their payload size
+    #[test]
+    #[should_panic(expected = "Rate limit exceeded: try again later")]
+    fn test_rate_limit_weighted_writes() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.update_config(Config {
+            rate_limit: Some(RateLimitConfig {
+                capacity: 3,
+                refill_interval: 10_000,
+            }),
+            ..Config::default()
+        });
+        context
+            .predecessor_account_id(accounts(1))
+            .block_timestamp(100);
+        testing_env!(context.build());
+        let record = |text: &str| ArchiveRecord {
+            text: text.to_string(),
+            created_at: 10,
+            in_reply_to: None,
+        };
+
+        // Act: A like of a missing tweet, a two-record import and a short link
+        assert!(contract.like_tweet(TweetId(42), None).is_none());
+        contract.import_archive_chunk(vec![record("Old one"), record("Old two")], None);
+        contract.register_short_link("https://near.org".to_string());
+
+        // Assert: Only the import records and the link used tokens
+        assert_eq!(contract.get_rate_limit_metrics().metered_writes, 3);
+
+        // Act: The bucket is empty, so even a one-record import is rejected
+        contract.import_archive_chunk(vec![record("Old three")], None);
+    }
+
+    /// Test that short link clicks draw from the clicker's rate limit
+    /// Similar to testing that a redirect endpoint sits behind the same API gateway limit
+    #[test]
+    #[should_panic(expected = "Rate limit exceeded: try again later")]
+    fn test_rate_limit_record_click() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.update_config(Config {
+            rate_limit: Some(RateLimitConfig {
+                capacity: 1,
+                refill_interval: 10_000,
+            }),
+            ..Config::default()
+        });
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        let code = contract.register_short_link("https://near.org".to_string());
+
+        // Act: Another account clicks twice within the same refill interval
+        context.predecessor_account_id(accounts(2));
+        testing_env!(context.build());
+        assert_eq!(contract.record_click(code.clone()), 1);
+        contract.record_click(code);
+    }
+
+    /// Test per-method usage counters
+    /// Similar to checking per-route request counts on an APM dashboard
+    #[test]
+    fn test_method_metrics() {
+        let context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        let tweet = contract.post_tweet("One".to_string(), None, None, None, None);
+        contract.post_tweet("Two".to_string(), None, None, None, None);
+        contract.like_tweet(tweet.id, None);
+
+        let metrics = contract.get_method_metrics();
+        let calls = |name: &str| {
+            metrics
+                .iter()
+                .find(|metric| metric.method == name)
+                .map(|metric| metric.calls)
+        };
+
+        // Assert: Write methods are counted, never-called ones report 0, views aren't listed
+        assert_eq!(calls("post_tweet"), Some(2));
+        assert_eq!(calls("like_tweet"), Some(1));
+        assert_eq!(calls("set_mode"), Some(0));
+        assert_eq!(calls("get_all_tweets"), None);
+        assert!(metrics
+            .iter()
+            .all(|metric| metric.kind == "call" || metric.kind == "owner"));
+
+        // Assert: Calling views leaves every counter unchanged
+        contract.get_all_tweets(None, None, None);
+        contract.get_tweet_by_id(tweet.id);
+        contract.get_method_metrics();
+        assert_eq!(contract.get_method_metrics(), metrics);
+    }
+
+    /// Test block-height anchored state snapshots
+    /// Similar to testing a scheduled checksum job and the audit endpoint listing its results
+    #[test]
+    fn test_snapshots() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        for text in ["one", "two", "three"] {
+            contract.post_tweet(text.to_string(), None, None, None, None);
+        }
+        contract.delete_tweet(TweetId(1));
+
+        // Act: The owner takes a snapshot at block 42, during a maintenance window
+        context.predecessor_account_id(accounts(0)).block_height(42);
+        testing_env!(context.build());
+        contract.set_mode(ContractMode::ReadOnly);
+        let snapshot = contract.create_snapshot();
+
+        // Assert: Counters are recorded at that block
+        assert_eq!(snapshot.id, 0);
+        assert_eq!(snapshot.block_height, 42);
+        assert_eq!(snapshot.tweet_count, 2);
+        assert_eq!(snapshot.next_tweet_id, 3);
+        assert_eq!(snapshot.recent_tweets, 2);
+
+        // Assert: Auditors can recompute the hash chain from the live tweet ids, oldest first
+        let mut expected = [0u8; 32];
+        for id in [0u64, 2] {
+            let mut chunk = expected.to_vec();
+            chunk.extend(id.to_le_bytes());
+            expected = near_sdk::env::sha256_array(&chunk);
+        }
+        assert_eq!(snapshot.recent_tweets_hash.0, expected.to_vec());
+
+        // Act: A later snapshot after another tweet
+        contract.set_mode(ContractMode::Active);
+        context.predecessor_account_id(accounts(1)).block_height(43);
+        testing_env!(context.build());
+        contract.post_tweet("four".to_string(), None, None, None, None);
+        context.predecessor_account_id(accounts(0));
+        testing_env!(context.build());
+        let later = contract.create_snapshot();
+
+        // Assert: Snapshots are listed oldest first and the digest moved on
+        assert_ne!(later.recent_tweets_hash, snapshot.recent_tweets_hash);
+        assert_eq!(
+            contract.get_snapshots(None, None),
+            vec![snapshot, later.clone()]
+        );
+        assert_eq!(contract.get_snapshots(Some(1), Some(5)), vec![later]);
+    }
+
+    /// Test that only the owner can take snapshots
+    /// Similar to testing that an /admin endpoint returns 403 for regular users
+    #[test]
+    #[should_panic(expected = "Only the contract owner can call this method")]
+    fn test_create_snapshot_requires_owner() {
+        let context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+
+        contract.create_snapshot();
+    }
+
+    /// Test Merkle inclusion proofs of tweets, against the latest and an older root
+    /// Similar to verifying a certificate transparency log proof instead of trusting the server
+    #[test]
+    fn test_inclusion_proofs() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        for i in 0..5 {
+            contract.post_tweet(format!("Tweet {}", i), None, None, None, None);
+        }
+        context.predecessor_account_id(accounts(0));
+        testing_env!(context.build());
+        let snapshot = contract.create_snapshot();
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        for i in 5..7 {
+            contract.post_tweet(format!("Tweet {}", i), None, None, None, None);
+        }
+        contract.delete_tweet(TweetId(2));
+
+        // Assert: Every tweet (deleted ones too) has a proof that verifies independently
+        let latest_root = contract.get_inclusion_proof(TweetId(0), None).unwrap().root;
+        for id in 0..7 {
+            let proof = contract.get_inclusion_proof(TweetId(id), None).unwrap();
+            assert_eq!(proof.tree_size, 7);
+            assert_eq!(proof.root, latest_root);
+            assert!(verify_inclusion(&proof), "proof of #{} does not verify", id);
+        }
+
+        // Assert: The leaf commits to the tweet, so a tampered copy fails to verify
+        let tweet = contract.get_tweet_by_id(TweetId(3)).unwrap();
+        let mut leaf = vec![0u8];
+        leaf.extend(tweet.id.0.to_le_bytes());
+        leaf.extend(tweet.timestamp.to_le_bytes());
+        leaf.extend(&tweet.content_hash.0);
+        leaf.extend(tweet.author.as_str().as_bytes());
+        let mut proof = contract.get_inclusion_proof(TweetId(3), None).unwrap();
+        assert_eq!(proof.leaf_hash.0, near_sdk::env::sha256(&leaf));
+        proof.leaf_hash = near_sdk::env::sha256(b"forged").into();
+        assert!(!verify_inclusion(&proof));
+
+        // Assert: Proofs against the snapshot's tree size match the snapshot's root
+        let old = contract
+            .get_inclusion_proof(TweetId(4), Some(snapshot.next_tweet_id))
+            .unwrap();
+        assert_eq!(old.root, snapshot.tweets_root);
+        assert!(verify_inclusion(&old));
+
+        // Assert: No proof for tweets outside the tree
+        assert!(contract.get_inclusion_proof(TweetId(7), None).is_none());
+        assert!(contract
+            .get_inclusion_proof(TweetId(5), Some(snapshot.next_tweet_id))
+            .is_none());
+    }
+
+    /// Test the status endpoint used for readiness checks
+    /// Similar to testing GET /status
+    #[test]
+    fn test_get_status() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.post_tweet("Soon deleted".to_string(), None, None, None, None);
+        contract.delete_tweet(TweetId(0));
+        let status = contract.get_status();
+
+        // Assert: Versions, flags and maintenance progress are reported
+        assert_eq!(status.contract_version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(status.state_schema_version, 2);
+        assert_eq!(status.mode, ContractMode::Active);
+        assert_eq!(status.gc_pending, 1);
+        assert_eq!(status.migration_pending, 0);
+
+        // Assert: The config digest changes with the config
+        context.predecessor_account_id(accounts(0));
+        testing_env!(context.build());
+        contract.update_config(Config {
+            max_onchain_bytes: Some(280),
+            ..Config::default()
+        });
+        assert_ne!(contract.get_status().config_digest, status.config_digest);
+    }
+
+    /// Test ActivityStreams views used by federation bridges
+    /// Similar to testing a GET endpoint with "Accept: application/activity+json"
+    #[test]
+    fn test_activitypub_views() {
+        // Setup: Post a tweet on 2024-01-31 12:00:00 UTC
+        let mut context = get_context(accounts(1));
+        context.block_timestamp(1_706_702_400 * 1_000_000_000);
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.post_tweet("Hello fediverse".to_string(), None, None, None, None);
+
+        // Assert: The Note carries ActivityStreams field names and an RFC 3339 date
+        let note =
+            near_sdk::serde_json::to_value(contract.get_activitypub_note(TweetId(0)).unwrap())
+                .unwrap();
+        assert_eq!(note["@context"], "https://www.w3.org/ns/activitystreams");
+        assert_eq!(note["type"], "Note");
+        assert_eq!(note["content"], "Hello fediverse");
+        assert_eq!(note["published"], "2024-01-31T12:00:00Z");
+        assert_eq!(
+            note["attributedTo"],
+            contract.get_activitypub_person(accounts(1)).id
+        );
+
+        // Assert: The Create activity wraps the same note
+        let create = contract.get_activitypub_create(TweetId(0)).unwrap();
+        assert_eq!(create.kind, "Create");
+        assert_eq!(create.object.content, "Hello fediverse");
+        assert!(contract.get_activitypub_note(TweetId(999)).is_none());
+    }
+
+    /// Test v2 cursor pagination
+    /// Similar to testing GET /v2/tweets?cursor=<id> following "next" links
+    #[test]
+    fn test_get_tweets_v2() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        for i in 0..5 {
+            // Alternate authors: even ids by user 1, odd ids by user 2
+            let author = if i % 2 == 0 { accounts(1) } else { accounts(2) };
+            context.predecessor_account_id(author);
+            testing_env!(context.build());
+            contract.post_tweet(format!("Tweet {}", i), None, None, None, None);
+        }
+        let ids = |page: &TweetPage| {
+            page.tweets
+                .iter()
+                .map(|tweet| tweet.id.0)
+                .collect::<Vec<_>>()
+        };
+
+        // Act & Assert: Pages are newest first and chained through next_cursor
+        let first = contract.get_tweets_v2(None, Some(2), None);
+        assert_eq!(ids(&first), vec![4, 3]);
+        let second = contract.get_tweets_v2(first.next_cursor, Some(2), None);
+        assert_eq!(ids(&second), vec![2, 1]);
+        let last = contract.get_tweets_v2(second.next_cursor, Some(2), None);
+        assert_eq!(ids(&last), vec![0]);
+        assert_eq!(last.next_cursor, None);
+
+        // Assert: Author pages only contain that author's tweets
+        let by_author = contract.get_tweets_by_author_v2(accounts(2), None, None, None);
+        assert_eq!(ids(&by_author), vec![3, 1]);
+    }
+
+    /// Simulate clients paging through the timeline while other users delete tweets
+    /// Similar to testing that a cursor-paginated API stays consistent under concurrent writes
+    #[test]
+    fn test_cursor_pagination_under_concurrent_deletes() {
+        const TWEETS: u64 = 40;
+
+        // Several deterministic "schedules" of deletes (a tiny xorshift PRNG, so failures replay)
+        for seed in 1..=20u64 {
+            let context = get_context(accounts(1));
+            testing_env!(context.build());
+            let mut contract = TwitterContract::new();
+            for i in 0..TWEETS {
+                contract.post_tweet(format!("Tweet {}", i), None, None, None, None);
+            }
+            let mut rng = seed;
+            let mut next_random = move || {
+                rng ^= rng << 13;
+                rng ^= rng >> 7;
+                rng ^= rng << 17;
+                rng
+            };
+
+            let mut seen: Vec<u64> = Vec::new();
+            let mut deleted: Vec<u64> = Vec::new();
+            let mut cursor = None;
+            loop {
+                // Act: Fetch one page, then delete a couple of random tweets before the next one
+                let page = contract.get_tweets_v2(cursor, Some(3), None);
+                seen.extend(page.tweets.iter().map(|tweet| tweet.id.0));
+                for _ in 0..2 {
+                    let tweet_id = next_random() % TWEETS;
+                    if contract.delete_tweet(TweetId(tweet_id)).is_some() {
+                        deleted.push(tweet_id);
+                    }
+                }
+
+                cursor = page.next_cursor;
+                if cursor.is_none() {
+                    break;
+                }
+            }
+
+            // Assert: No duplicates, newest first across pages, and no surviving tweet skipped
+            assert!(
+                seen.windows(2).all(|pair| pair[0] > pair[1]),
+                "seed {}",
+                seed
+            );
+            for tweet_id in 0..TWEETS {
+                if !deleted.contains(&tweet_id) {
+                    assert!(
+                        seen.contains(&tweet_id),
+                        "seed {}: skipped #{}",
+                        seed,
+                        tweet_id
+                    );
+                }
+            }
+        }
+    }
+
+    /// Test importing tweets from another platform's archive
+    /// Similar to testing a bulk import endpoint
+    #[test]
+    fn test_import_archive_chunk() {
+        let mut context = get_context(accounts(1));
+        context.block_timestamp(1_000);
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+
+        // Act
+        let tweet_ids = contract.import_archive_chunk(
+            vec![
+                ArchiveRecord {
+                    text: "My first tweet, from 2009".to_string(),
+                    created_at: 10,
+                    in_reply_to: None,
+                },
+                ArchiveRecord {
+                    text: "A reply".to_string(),
+                    created_at: 20,
+                    in_reply_to: Some("1234567890".to_string()),
+                },
+            ],
+            None,
+        );
+
+        // Assert: Tweets are recreated under the caller with the imported flag
+        assert_eq!(tweet_ids.len(), 2);
+        let tweet = contract.get_tweet_by_id(tweet_ids[0]).unwrap();
+        assert_eq!(tweet.author, accounts(1));
+        assert!(tweet.imported);
+        assert_eq!(tweet.original_created_at, Some(10));
+        assert_eq!(tweet.timestamp, 1_000);
+        assert_eq!(
+            contract
+                .search_tweets("2009".to_string(), None, None, None)
+                .len(),
+            1
+        );
+
+        // Assert: Regular tweets are not flagged
+        let regular = contract.post_tweet("New".to_string(), None, None, None, None);
+        assert!(!regular.imported);
+        assert_eq!(regular.original_created_at, None);
+    }
+
+    /// Test that only the owner can import into another account
+    #[test]
+    #[should_panic(expected = "Only the contract owner can call this method")]
+    fn test_import_archive_chunk_for_other_account() {
+        let mut context = get_context(accounts(1));
+        context.block_timestamp(1_000);
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+
+        let record = ArchiveRecord {
+            text: "Not yours".to_string(),
+            created_at: 10,
+            in_reply_to: None,
+        };
+        contract.import_archive_chunk(vec![record], Some(accounts(2)));
+    }
+
+    /// Test that archive records from the future are rejected
+    #[test]
+    #[should_panic(expected = "Record 0: created_at must be in the past")]
+    fn test_import_archive_chunk_from_future() {
+        let mut context = get_context(accounts(1));
+        context.block_timestamp(1_000);
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+
+        let record = ArchiveRecord {
+            text: "Time travel".to_string(),
+            created_at: 2_000,
+            in_reply_to: None,
+        };
+        contract.import_archive_chunk(vec![record], None);
+    }
+
+    /// Test paging through an account's data export
+    /// Similar to testing a "download your data" endpoint
+    #[test]
+    fn test_export_my_data() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.post_tweet("Mine 0".to_string(), None, None, None, None);
+        contract.post_tweet("Mine 1".to_string(), None, None, None, None);
+        context.predecessor_account_id(accounts(2));
+        testing_env!(context.build());
+        contract.post_tweet("Not mine".to_string(), None, None, None, None);
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        contract.post_tweet("Mine 3".to_string(), None, None, None, None);
+        contract.update_settings(SettingsPatch {
+            language: Some("en".to_string()),
+            ..Default::default()
+        });
+
+        // Act: Page through the tweets section
+        let first = contract.export_my_data(accounts(1), "tweets".to_string(), None, Some(2));
+        let second = contract.export_my_data(
+            accounts(1),
+            "tweets".to_string(),
+            first.next_cursor,
+            Some(2),
+        );
+
+        // Assert: Only the account's tweets, in the same JSON shape as the regular views
+        let texts: Vec<_> = first
+            .records
+            .iter()
+            .chain(&second.records)
+            .map(|record| record["text"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(texts, vec!["Mine 3", "Mine 1", "Mine 0"]);
+        assert_eq!(second.next_cursor, None);
+
+        // Assert: Small sections come in one page
+        let settings = contract.export_my_data(accounts(1), "settings".to_string(), None, None);
+        assert_eq!(settings.records.len(), 1);
+        assert_eq!(settings.records[0]["language"], "en");
+        assert_eq!(settings.next_cursor, None);
+    }
+
+    /// Test the export sections for personas, short links and RSVPs
+    #[test]
+    fn test_export_my_data_personas_links_rsvps() {
+        // Setup: User 2 announces two events, user 1 creates a persona, a short link and RSVPs
+        let mut context = get_context(accounts(2));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        let meetup = contract.post_event(
+            "Meetup".to_string(),
+            1_000,
+            "Lisbon".to_string(),
+            String::new(),
+        );
+        let hackathon = contract.post_event(
+            "Hackathon".to_string(),
+            2_000,
+            "Lisbon".to_string(),
+            String::new(),
+        );
+        contract.create_persona("not_mine".to_string());
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        contract.create_persona("brand".to_string());
+        let code = contract.register_short_link("https://near.org".to_string());
+        contract.rsvp(meetup.tweet_id, RsvpStatus::Going);
+        contract.rsvp(hackathon.tweet_id, RsvpStatus::Interested);
+
+        // Act: Export the three sections
+        let personas = contract.export_my_data(accounts(1), "personas".to_string(), None, None);
+        let links = contract.export_my_data(accounts(1), "short_links".to_string(), None, None);
+        let first = contract.export_my_data(accounts(1), "rsvps".to_string(), None, Some(1));
+        let second =
+            contract.export_my_data(accounts(1), "rsvps".to_string(), first.next_cursor, Some(1));
+
+        // Assert: Only the account's own records, RSVPs newest event first with the event id
+        assert_eq!(personas.records.len(), 1);
+        assert_eq!(personas.records[0]["handle"], "brand");
+        assert_eq!(links.records.len(), 1);
+        assert_eq!(links.records[0]["code"], code.as_str());
+        assert_eq!(first.records.len(), 1);
+        assert_eq!(first.records[0]["tweet_id"], hackathon.tweet_id.0);
+        assert_eq!(first.records[0]["status"], "Interested");
+        assert_eq!(second.records.len(), 1);
+        assert_eq!(second.records[0]["tweet_id"], meetup.tweet_id.0);
+    }
+
+    /// Test that unknown export sections are rejected
+    #[test]
+    #[should_panic(expected = "Unknown export section: bookmarks")]
+    fn test_export_my_data_unknown_section() {
+        let context = get_context(accounts(1));
+        testing_env!(context.build());
+        let contract = TwitterContract::new();
+
+        contract.export_my_data(accounts(1), "bookmarks".to_string(), None, None);
+    }
+
+    /// Test the machine-readable API catalog
+    /// Similar to testing that GET /openapi.json describes the real endpoints
+    #[test]
+    fn test_get_api_spec() {
+        let context = get_context(accounts(1));
+        testing_env!(context.build());
+        let contract = TwitterContract::new();
+
+        // Act
+        let spec = contract.get_api_spec();
+
+        // Assert: Method names are unique and the kinds/pagination styles are described
+        let mut names: Vec<&str> = spec
+            .methods
+            .iter()
+            .map(|method| method.name.as_str())
+            .collect();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), spec.methods.len());
+        let method = |name: &str| {
+            spec.methods
+                .iter()
+                .find(|method| method.name == name)
+                .unwrap()
+        };
+        assert_eq!(method("post_tweet").kind, "call");
+        assert_eq!(method("set_mode").kind, "owner");
+        assert_eq!(
+            method("get_all_tweets").pagination.as_deref(),
+            Some("offset")
+        );
+        assert_eq!(
+            method("get_tweets_v2").pagination.as_deref(),
+            Some("cursor")
+        );
+        assert_eq!(method("get_tweet_by_id").args, vec!["tweet_id: u64"]);
+        assert!(spec
+            .errors
+            .contains(&"Only the contract owner can call this method".to_string()));
+    }
+
+    /// Test deterministic demo data seeding
+    /// Similar to testing a `db:seed` task against an empty database
+    #[cfg(feature = "demo-seed")]
+    #[test]
+    fn test_seed_demo_data() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+
+        // Act
+        let created = contract.seed_demo_data(3, 10);
+
+        // Assert: Tweets are spread over the demo accounts and some are co-authored
+        assert_eq!(created, 10);
+        let tweets = contract.get_all_tweets(None, Some(10), None);
+        assert_eq!(tweets.len(), 10);
+        assert_eq!(tweets[0].author.as_str(), format!("user0.{}", accounts(0)));
+        assert_eq!(tweets[1].author.as_str(), format!("user1.{}", accounts(0)));
+        assert_eq!(tweets[4].coauthors.len(), 1);
+        assert!(tweets.iter().any(|tweet| tweet.likes > 0));
+
+        // Assert: The same arguments produce the same content on a fresh deployment
+        testing_env!(context.build());
+        let mut other = TwitterContract::new();
+        other.seed_demo_data(3, 10);
+        assert_eq!(other.get_all_tweets(None, Some(10), None), tweets);
+    }
+
+    /// Test sparse field selection on tweet pages
+    /// Similar to testing GET /tweets?fields=id,text,likes
+    #[test]
+    fn test_get_all_tweets_sparse() {
+        let context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        contract.post_tweet("Sparse".to_string(), None, None, None, None);
+
+        // Act: Request only a few fields
+        let fields = Some(vec![
+            "id".to_string(),
+            "text".to_string(),
+            "likes".to_string(),
+        ]);
+        let page = contract.get_all_tweets_sparse(None, None, fields, None);
+
+        // Assert: Only the requested fields are returned
+        let tweet = page[0].as_object().unwrap();
+        assert_eq!(tweet.len(), 3);
+        assert_eq!(tweet["id"], 0);
+        assert_eq!(tweet["text"], "Sparse");
+        assert_eq!(tweet["likes"], 0);
+
+        // Assert: Without a selection, every field is returned
+        let full = contract.get_all_tweets_sparse(None, None, None, None);
+        assert!(full[0].get("author").is_some());
+    }
+
+    /// Test timestamp-keyed pagination for infinite scroll and polling
+    /// Similar to testing GET /tweets?before=<ts> and GET /tweets?after=<ts>
+    #[test]
+    fn test_get_tweets_before_and_after() {
+        // Setup: Tweets 0, 1, 2, 3 posted at t=10, 20, 20, 30
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+        let mut contract = TwitterContract::new();
+        for (text, timestamp) in [("a", 10), ("b", 20), ("c", 20), ("d", 30)] {
+            context.block_timestamp(timestamp);
+            testing_env!(context.build());
+            contract.post_tweet(text.to_string(), None, None, None, None);
+        }
+        let ids = |tweets: Vec<Tweet>| tweets.iter().map(|tweet| tweet.id.0).collect::<Vec<_>>();
+
+        // Assert: Before is exclusive and newest first, after is exclusive and oldest first
+        assert_eq!(
+            ids(contract.get_tweets_before(30, None, None)),
+            vec![2, 1, 0]
+        );
+        assert_eq!(
+            ids(contract.get_tweets_before(30, Some(2), None)),
+            vec![2, 1]
+        );
+        assert_eq!(
+            ids(contract.get_tweets_after(10, None, None)),
+            vec![1, 2, 3]
+        );
+        assert_eq!(ids(contract.get_tweets_after(20, None, None)), vec![3]);
+        assert!(contract.get_tweets_after(30, None, None).is_empty());
+
+        // Assert: Deleted tweets are skipped without shifting the cursor
+        contract.delete_tweet(TweetId(1));
+        assert_eq!(
+            ids(contract.get_tweets_before(30, Some(2), None)),
+            vec![2, 0]
+        );
     }
 
     /// Test getting all tweets with pagination
@@ -213,12 +3403,12 @@ mod tests {
         let mut contract = TwitterContract::new();
 
         // Create test data
-        contract.post_tweet("First tweet".to_string());
-        contract.post_tweet("Second tweet".to_string());
-        contract.post_tweet("Third tweet".to_string());
+        contract.post_tweet("First tweet".to_string(), None, None, None, None);
+        contract.post_tweet("Second tweet".to_string(), None, None, None, None);
+        contract.post_tweet("Third tweet".to_string(), None, None, None, None);
 
         // Test: Get all tweets (no pagination)
-        let all_tweets = contract.get_all_tweets(None, None);
+        let all_tweets = contract.get_all_tweets(None, None, None);
         assert_eq!(all_tweets.len(), 3);
         assert_eq!(all_tweets[0].text, "First tweet");
         assert_eq!(all_tweets[1].text, "Second tweet");
@@ -226,7 +3416,7 @@ mod tests {
 
         // Test: Pagination - skip first tweet, get only 1 tweet
         // This is like calling GET /tweets?offset=1&limit=1
-        let limited_tweets = contract.get_all_tweets(Some(1), Some(1));
+        let limited_tweets = contract.get_all_tweets(Some(1), Some(1), None);
         assert_eq!(limited_tweets.len(), 1);
         assert_eq!(limited_tweets[0].text, "Second tweet");
     }
@@ -241,27 +3431,33 @@ mod tests {
         let mut contract = TwitterContract::new();
 
         // User 1 posts tweets
-        contract.post_tweet("Tweet from user 1".to_string());
-        contract.post_tweet("Another tweet from user 1".to_string());
+        contract.post_tweet("Tweet from user 1".to_string(), None, None, None, None);
+        contract.post_tweet(
+            "Another tweet from user 1".to_string(),
+            None,
+            None,
+            None,
+            None,
+        );
 
         // Switch to user 2 (like logging in as different user)
         context.predecessor_account_id(accounts(2));
         testing_env!(context.build());
-        contract.post_tweet("Tweet from user 2".to_string());
+        contract.post_tweet("Tweet from user 2".to_string(), None, None, None, None);
 
         // Test: Get tweets by user 1
-        let user1_tweets = contract.get_tweets_by_author(accounts(1), None, None);
+        let user1_tweets = contract.get_tweets_by_author(accounts(1), None, None, None);
         assert_eq!(user1_tweets.len(), 2);
         assert_eq!(user1_tweets[0].author, accounts(1));
         assert_eq!(user1_tweets[1].author, accounts(1));
 
         // Test: Get tweets by user 2
-        let user2_tweets = contract.get_tweets_by_author(accounts(2), None, None);
+        let user2_tweets = contract.get_tweets_by_author(accounts(2), None, None, None);
         assert_eq!(user2_tweets.len(), 1);
         assert_eq!(user2_tweets[0].author, accounts(2));
 
         // Test: Get tweets by non-existent user (edge case)
-        let no_tweets = contract.get_tweets_by_author(accounts(3), None, None);
+        let no_tweets = contract.get_tweets_by_author(accounts(3), None, None, None);
         assert_eq!(no_tweets.len(), 0);
     }
 }
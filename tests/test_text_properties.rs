@@ -0,0 +1,100 @@
+// ================================================================================================
+// PROPERTY-BASED TESTS FOR TEXT HANDLING
+// ================================================================================================
+// Instead of hand-picked examples, proptest generates hundreds of random inputs (including
+// adversarial Unicode: combining marks, RTL text, emoji, lone "\r", case-changing letters) and
+// checks that a property holds for every one of them. When a case fails, proptest shrinks it
+// to the smallest input that still fails - like a fuzzer with readable bug reports.
+//
+// The fuzz target in fuzz/ covers the same code paths with coverage-guided fuzzing.
+
+use near_sdk::{test_utils::VMContextBuilder, testing_env, AccountId};
+use near_twitter_example_rs::TwitterContract;
+use proptest::prelude::*;
+
+// Fresh contract with "alice.near" as the caller (like a clean in-memory database per case)
+fn new_contract() -> TwitterContract {
+    let mut builder = VMContextBuilder::new();
+    builder.predecessor_account_id("alice.near".parse::<AccountId>().unwrap());
+    testing_env!(builder.build());
+    TwitterContract::new()
+}
+
+// Arbitrary Unicode text, with a bias towards characters that tend to break text handling
+fn adversarial_text() -> impl Strategy<Value = String> {
+    prop::collection::vec(
+        prop_oneof![
+            any::<char>(),
+            prop::sample::select(vec![
+                '\r', '\n', ' ', '\t', '#', '@', '\u{200B}', '\u{0307}', '\u{202E}', 'İ', 'ß', 'Σ',
+                'ﬁ', '😀', 'a', 'Z', '9',
+            ]),
+        ],
+        0..200,
+    )
+    .prop_map(|chars| chars.into_iter().collect())
+}
+
+proptest! {
+    /// Posting any text never panics and stores it unchanged
+    /// Similar to fuzzing a POST /tweets endpoint with random request bodies
+    #[test]
+    fn prop_post_tweet_never_panics(text in adversarial_text()) {
+        let mut contract = new_contract();
+
+        let tweet = contract.post_tweet(text.clone(), None, None, None, None);
+
+        prop_assert_eq!(&tweet.text, &text);
+        prop_assert_eq!(contract.get_tweet_by_id(tweet.id), Some(tweet.value));
+    }
+
+    /// The content hash only ignores line-ending style and surrounding whitespace
+    /// Similar to testing that an ETag is stable across equivalent representations
+    #[test]
+    fn prop_content_hash_is_canonical(text in adversarial_text()) {
+        // Start from Unix line endings so the CRLF variant below is a pure re-encoding
+        let text = text.replace('\r', "");
+        let mut contract = new_contract();
+        let tweet = contract.post_tweet(text.clone(), None, None, None, None);
+
+        prop_assert!(contract.verify_tweet_content(tweet.id, text.clone()));
+        prop_assert!(contract.verify_tweet_content(tweet.id, text.replace('\n', "\r\n")));
+        let padded = format!(" {}\n", text);
+        prop_assert!(contract.verify_tweet_content(tweet.id, padded));
+        let altered = format!("{}x", text.trim_end());
+        prop_assert!(!contract.verify_tweet_content(tweet.id, altered));
+    }
+
+    /// Every indexed word finds its tweet, and search never returns a tweet without the word
+    /// Similar to testing that a full-text index has no false negatives or key collisions
+    #[test]
+    fn prop_search_index_keys_do_not_collide(
+        first in adversarial_text(),
+        second in adversarial_text(),
+        // Ends with a digit, so it is never a stopword
+        word in "[\\p{L}\\p{N}]{1,9}[0-9]",
+    ) {
+        let mut contract = new_contract();
+        // The word comes first, so the per-tweet term limit can't push it out of the index
+        let text = format!("{} {} {}", word, first, second);
+        let tweet = contract.post_tweet(text, None, None, None, None);
+        let other = contract.post_tweet(first.clone(), None, None, None, None);
+
+        let results = contract.search_tweets(word.clone(), None, Some(100), None);
+
+        prop_assert!(results.iter().any(|result| result.id == tweet.id));
+        let needle = word.to_lowercase();
+        for result in &results {
+            prop_assert!(contains_word(&result.text, &needle));
+        }
+        let other_matches = contains_word(&other.text, &needle);
+        prop_assert!(other_matches || results.iter().all(|result| result.id != other.id));
+    }
+}
+
+// Whether `text` contains `needle` as a whole word, compared the way the search index does
+// (split on non-alphanumeric characters, then lowercase each word on its own)
+fn contains_word(text: &str, needle: &str) -> bool {
+    text.split(|c: char| !c.is_alphanumeric())
+        .any(|word| word.to_lowercase() == needle)
+}
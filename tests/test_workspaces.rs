@@ -0,0 +1,147 @@
+// ================================================================================================
+// SANDBOX END-TO-END TESTS
+// ================================================================================================
+// Unlike test_basics.rs (which calls the Rust methods in memory), these tests compile the contract
+// to WASM, deploy it to a local NEAR sandbox node and send real transactions from several accounts.
+// Think of it as the difference between unit-testing a controller and running API tests against
+// a docker-compose stack: serialization, gas, logs and cross-account permissions are all real.
+//
+// These are slow, so they are opt-in (CI runs them in their own job):
+// cargo test --test test_workspaces -- --ignored (downloads the sandbox binary on first run)
+
+use near_workspaces::types::Gas;
+use near_workspaces::{Account, Contract};
+use serde_json::{json, Value};
+
+// Upper bound on the gas a single post/like/delete may burn - a regression guard, not a target
+const MAX_WRITE_GAS: Gas = Gas::from_tgas(20);
+
+// Deploy a fresh contract and create two user accounts (like seeding a test database)
+async fn setup() -> Result<(Contract, Account, Account), Box<dyn std::error::Error>> {
+    let contract_wasm = near_workspaces::compile_project("./").await?;
+    let sandbox = near_workspaces::sandbox().await?;
+    let contract = sandbox.dev_deploy(&contract_wasm).await?;
+    let outcome = contract.call("new").transact().await?;
+    assert!(
+        outcome.is_success(),
+        "{:#?}",
+        outcome.into_result().unwrap_err()
+    );
+
+    let alice = sandbox.dev_create_account().await?;
+    let bob = sandbox.dev_create_account().await?;
+    Ok((contract, alice, bob))
+}
+
+// Parse the NEP-297 events out of a transaction's logs
+fn events(logs: &[&str]) -> Vec<Value> {
+    logs.iter()
+        .filter_map(|log| log.strip_prefix("EVENT_JSON:"))
+        .map(|event| serde_json::from_str(event).expect("Events are valid JSON"))
+        .collect()
+}
+
+/// Test the post -> like -> delete flow across accounts
+/// Similar to an API test where two logged-in users interact with the same resource
+#[tokio::test]
+#[ignore = "slow: run with `cargo test --test test_workspaces -- --ignored`"]
+async fn test_multi_account_flow() -> Result<(), Box<dyn std::error::Error>> {
+    let (contract, alice, bob) = setup().await?;
+
+    // Act: Alice posts a tweet
+    let outcome = alice
+        .call(contract.id(), "post_tweet")
+        .args_json(json!({ "text": "Hello from the sandbox!" }))
+        .transact()
+        .await?;
+
+    // Assert: The tweet is returned, the event is emitted and gas stays bounded
+    assert!(outcome.is_success(), "{:#?}", outcome.outcomes());
+    assert!(outcome.total_gas_burnt <= MAX_WRITE_GAS);
+    let posted_events = events(&outcome.logs());
+    assert_eq!(posted_events.len(), 1);
+    assert_eq!(posted_events[0]["event"], "tweet_posted");
+    let tweet: Value = outcome.json()?;
+    assert_eq!(tweet["author"], alice.id().as_str());
+    let tweet_id = tweet["id"].clone();
+
+    // Act: Bob likes it
+    let outcome = bob
+        .call(contract.id(), "like_tweet")
+        .args_json(json!({ "tweet_id": tweet_id }))
+        .transact()
+        .await?;
+
+    // Assert
+    assert!(outcome.is_success(), "{:#?}", outcome.outcomes());
+    assert!(outcome.total_gas_burnt <= MAX_WRITE_GAS);
+    assert_eq!(events(&outcome.logs())[0]["event"], "tweet_liked");
+    let liked: Value = outcome.json()?;
+    assert_eq!(liked["likes"], 1);
+
+    // Act: Bob tries to delete Alice's tweet (403 Forbidden in REST terms)
+    let outcome = bob
+        .call(contract.id(), "delete_tweet")
+        .args_json(json!({ "tweet_id": tweet_id }))
+        .transact()
+        .await?;
+
+    // Assert: Nothing is deleted and no event is emitted
+    assert!(outcome.is_success(), "{:#?}", outcome.outcomes());
+    assert!(events(&outcome.logs()).is_empty());
+    assert_eq!(outcome.json::<Option<u64>>()?, None);
+    let stored: Option<Value> = contract
+        .view("get_tweet_by_id")
+        .args_json(json!({ "tweet_id": tweet_id }))
+        .await?
+        .json()?;
+    assert!(stored.is_some());
+
+    // Act: Alice deletes her own tweet
+    let outcome = alice
+        .call(contract.id(), "delete_tweet")
+        .args_json(json!({ "tweet_id": tweet_id }))
+        .transact()
+        .await?;
+
+    // Assert
+    assert!(outcome.is_success(), "{:#?}", outcome.outcomes());
+    assert!(outcome.total_gas_burnt <= MAX_WRITE_GAS);
+    assert_eq!(events(&outcome.logs())[0]["event"], "tweet_deleted");
+    let stored: Option<Value> = contract
+        .view("get_tweet_by_id")
+        .args_json(json!({ "tweet_id": tweet_id }))
+        .await?
+        .json()?;
+    assert!(stored.is_none());
+
+    Ok(())
+}
+
+/// Test that owner-only methods reject other accounts on a real node
+/// Similar to testing that /admin endpoints return 403 for regular users
+#[tokio::test]
+#[ignore = "slow: run with `cargo test --test test_workspaces -- --ignored`"]
+async fn test_owner_methods_reject_other_accounts() -> Result<(), Box<dyn std::error::Error>> {
+    let (contract, alice, _) = setup().await?;
+
+    // Act
+    let outcome = alice
+        .call(contract.id(), "set_mode")
+        .args_json(json!({ "mode": "ReadOnly" }))
+        .transact()
+        .await?;
+
+    // Assert: The transaction fails with the owner error and the mode is unchanged
+    assert!(outcome.is_failure());
+    let error = format!("{:?}", outcome.into_result().unwrap_err());
+    assert!(error.contains("Only the contract owner can call this method"));
+    let status: Value = contract
+        .view("get_status")
+        .args_json(json!({}))
+        .await?
+        .json()?;
+    assert_eq!(status["mode"], "Active");
+
+    Ok(())
+}